@@ -2,3 +2,11 @@ use anchor_lang::prelude::*;
 
 #[constant]
 pub const SEED: &str = "anchor";
+
+/// Highest royalty an artist can set on an accessory (in basis point)
+#[constant]
+pub const MAX_ROYALTY_BPS: u16 = 2_500;
+
+/// Longest message that can be attached to a gifted accessory (in bytes)
+#[constant]
+pub const MAX_GIFT_MESSAGE_LEN: usize = 64;
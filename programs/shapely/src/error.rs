@@ -10,4 +10,214 @@ pub enum ShapelyError {
 
     #[msg("Avatar mint is not verified in the collection")]
     AvatarNotVerified,
+
+    #[msg("Signer is not the config admin")]
+    Unauthorized,
+
+    #[msg("Signer is not the artist that minted the accessory")]
+    NotAccessoryArtist,
+
+    #[msg("Metadata updates are disabled for this config")]
+    MetadataUpdatesDisabled,
+
+    #[msg("Accessory metadata is locked")]
+    MetadataLocked,
+
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+
+    #[msg("Drop account is required while a drop is active")]
+    MissingDrop,
+
+    #[msg("Drop has not started yet")]
+    DropNotStarted,
+
+    #[msg("Drop has ended")]
+    DropEnded,
+
+    #[msg("Drop is sold out")]
+    DropSoldOut,
+
+    #[msg("Drop window is invalid")]
+    InvalidDropWindow,
+
+    #[msg("Uri does not match the drop uri prefix")]
+    InvalidDropUri,
+
+    #[msg("Wallet is not in the drop allowlist")]
+    NotAllowlisted,
+
+    #[msg("Allowlist claim account is required while the allowlist is active")]
+    MissingAllowlistClaim,
+
+    #[msg("Artist has reached the accessory mint cap")]
+    MintCapReached,
+
+    #[msg("Mint cap period cannot be negative")]
+    InvalidMintCapPeriod,
+
+    #[msg("Royalty exceeds the maximum allowed")]
+    RoyaltyTooHigh,
+
+    #[msg("Creator account does not match the accessory artist")]
+    InvalidCreator,
+
+    #[msg("Too many payment mints")]
+    TooManyPaymentMints,
+
+    #[msg("Payment mint is not accepted")]
+    PaymentMintNotAccepted,
+
+    #[msg("Payment mint does not match the listing")]
+    InvalidPaymentMint,
+
+    #[msg("Token accounts are required to pay for this listing")]
+    MissingPaymentAccounts,
+
+    #[msg("Listing expiry must be in the future")]
+    InvalidListingExpiry,
+
+    #[msg("Listing has expired")]
+    ListingExpired,
+
+    #[msg("Listing has not expired")]
+    ListingNotExpired,
+
+    #[msg("Listing price does not match the expected price")]
+    UnexpectedPrice,
+
+    #[msg("Listing payment mint does not match the expected payment mint")]
+    UnexpectedPaymentMint,
+
+    #[msg("Offer amount must be greater than zero")]
+    InvalidOfferAmount,
+
+    #[msg("Accessory does not match the offer filter")]
+    OfferFilterMismatch,
+
+    #[msg("Auction end time must be in the future")]
+    InvalidAuctionEndTime,
+
+    #[msg("Minimum bid increment must be greater than zero")]
+    InvalidBidIncrement,
+
+    #[msg("Auction has ended")]
+    AuctionEnded,
+
+    #[msg("Auction has not ended")]
+    AuctionNotEnded,
+
+    #[msg("Bid is lower than the minimum bid")]
+    BidTooLow,
+
+    #[msg("Previous bidder account does not match the highest bidder")]
+    InvalidPreviousBidder,
+
+    #[msg("Winner account does not match the auction winner")]
+    InvalidAuctionWinner,
+
+    #[msg("Dutch auction price schedule is invalid")]
+    InvalidDutchAuction,
+
+    #[msg("Dutch auction listings cannot be repriced")]
+    DutchAuctionRepricing,
+
+    #[msg("Bundle accounts are invalid")]
+    InvalidBundleAccounts,
+
+    #[msg("Swap accounts are invalid")]
+    InvalidSwapAccounts,
+
+    #[msg("Swap must request an accessory or a SOL top-up")]
+    InvalidSwapTerms,
+
+    #[msg("Listing vault or delegated token accounts are missing or invalid")]
+    InvalidListingAccounts,
+
+    #[msg("Referral share cannot exceed the whole fee")]
+    InvalidReferralBps,
+
+    #[msg("Recipient does not hold the avatar")]
+    AvatarNotHeld,
+
+    #[msg("Gift message is too long")]
+    GiftMessageTooLong,
+
+    #[msg("Rental period and price must be greater than zero")]
+    InvalidRentalTerms,
+
+    #[msg("Number of rental periods is out of range")]
+    InvalidRentalPeriods,
+
+    #[msg("Accessory is currently rented")]
+    AccessoryRented,
+
+    #[msg("Accessory is not rented")]
+    AccessoryNotRented,
+
+    #[msg("Rental has not ended")]
+    RentalNotEnded,
+
+    #[msg("Renter account does not match the current renter")]
+    InvalidRenter,
+
+    #[msg("Claimable proceeds are only supported for listings priced in SOL")]
+    ClaimableProceedsSolOnly,
+
+    #[msg("Seller proceeds account is missing")]
+    MissingSellerProceeds,
+
+    #[msg("There are no proceeds to claim")]
+    NothingToClaim,
+
+    #[msg("Creator shares must be unique, non-zero and add up to 100")]
+    InvalidCreatorShares,
+
+    #[msg("Buyer must hold an avatar for this purchase")]
+    AvatarRequired,
+
+    #[msg("Cart accounts are invalid")]
+    InvalidCartAccounts,
+
+    #[msg("Only SOL listings held in a listing vault can be bought in a cart")]
+    UnsupportedCartListing,
+
+    #[msg("Listing is reserved for another buyer")]
+    ReservedForAnotherBuyer,
+
+    #[msg("Accessory metadata has no collection")]
+    AccessoryMissingCollection,
+
+    #[msg("Accessory belongs to a different collection")]
+    AccessoryWrongCollection,
+
+    #[msg("Avatar metadata has no collection")]
+    AvatarMissingCollection,
+
+    #[msg("Avatar belongs to a different collection")]
+    AvatarWrongCollection,
+
+    #[msg("Config already uses the current layout")]
+    ConfigAlreadyMigrated,
+
+    #[msg("Too many referrers")]
+    TooManyReferrers,
+
+    #[msg("Referrer is not an approved frontend or is a party to the sale")]
+    InvalidReferrer,
+
+    #[msg("Sender does not hold the accessory")]
+    AccessoryNotHeld,
+
+    #[msg("Seller payout account is missing")]
+    MissingSellerPayout,
+
+    #[msg("Metadata account does not belong to the mint")]
+    MetadataMintMismatch,
+
+    #[msg("Listing already uses the current layout")]
+    ListingAlreadyMigrated,
+
+    #[msg("Swap was made under a different config")]
+    SwapConfigMismatch,
 }
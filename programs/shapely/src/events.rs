@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct AccessoryListed {
+    pub listing: Pubkey,
+    pub accessory_mint: Pubkey,
+    pub seller: Pubkey,
+    pub price: u64,
+    pub payment_mint: Option<Pubkey>,
+    pub reserved_buyer: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ListingPriceUpdated {
+    pub listing: Pubkey,
+    pub accessory_mint: Pubkey,
+    pub seller: Pubkey,
+    pub payment_mint: Option<Pubkey>,
+    pub reserved_buyer: Option<Pubkey>,
+    pub old_price: u64,
+    pub new_price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralFeePaid {
+    pub listing: Pubkey,
+    pub accessory_mint: Pubkey,
+    pub buyer: Pubkey,
+    pub referrer: Pubkey,
+    pub payment_mint: Option<Pubkey>,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AccessoryGifted {
+    pub accessory_mint: Pubkey,
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub avatar_mint: Pubkey,
+    pub message: Option<String>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProceedsClaimed {
+    pub seller: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{Metadata, MetadataAccount},
+    token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+use crate::{
+    error::ShapelyError,
+    payment::{transfer_lamports, SaleSplit},
+    state::{AccessoryRecord, Config, Offer},
+};
+
+#[derive(Accounts)]
+pub struct AcceptOffer<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(mut)]
+    /// CHECK: This will be validated in the offer seeds
+    pub buyer: AccountInfo<'info>,
+
+    /// The avatar accounts are only needed when the config avatar policy requires it
+    #[account(
+        seeds = [b"avatar", buyer.key().as_ref(), config.avatar_collection.key().as_ref()],
+        bump,
+    )]
+    pub buyer_avatar_mint: Option<Box<Account<'info, Mint>>>,
+
+    /// Validated against the avatar mint and collection in the handler
+    pub buyer_avatar_metadata: Option<Box<Account<'info, MetadataAccount>>>,
+
+    pub accessory_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"accessory", accessory_mint.key().as_ref()],
+        bump = accessory_record.bump
+    )]
+    pub accessory_record: Box<Account<'info, AccessoryRecord>>,
+
+    #[account(
+        mut,
+        associated_token::mint = accessory_mint,
+        associated_token::authority = seller
+    )]
+    pub seller_accessory_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = accessory_mint,
+        associated_token::authority = buyer
+    )]
+    pub buyer_accessory_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        close = buyer,
+        seeds = [b"offer", accessory_mint.key().as_ref(), buyer.key().as_ref()],
+        bump = offer.bump
+    )]
+    pub offer: Account<'info, Offer>,
+
+    #[account(seeds = [b"config", config.seed.to_le_bytes().as_ref()], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(mut, seeds = [b"treasury", config.key().as_ref()], bump = config.treasury_bump)]
+    pub treasury: SystemAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub metadata_program: Program<'info, Metadata>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AcceptOffer<'info> {
+    /// Remaining accounts hold one `[creator (mut)]` per creator in the accessory record
+    pub fn accept_offer(&mut self, creator_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        self.verify_buyer_avatar()?;
+        self.process_payment(creator_accounts)?;
+        self.deliver_nft()?;
+        self.lock_metadata()?;
+        Ok(())
+    }
+
+    pub fn verify_buyer_avatar(&self) -> Result<()> {
+        let primary_sale = self.seller.key() == self.accessory_record.artist;
+        let avatar_mint = self.buyer_avatar_mint.as_ref().map(|mint| mint.key());
+        let avatar = self
+            .buyer_avatar_metadata
+            .as_deref()
+            .zip(avatar_mint.as_ref())
+            .map(|(metadata, mint)| (&**metadata, mint));
+
+        self.config.verify_buyer_avatar(avatar, primary_sale)
+    }
+
+    /// Pays out the escrowed offer, the offer rent goes back to the buyer when it is closed
+    pub fn process_payment(&mut self, creator_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        require!(
+            creator_accounts.len() == self.accessory_record.creators.len(),
+            ShapelyError::InvalidCreator
+        );
+
+        let seller = self.seller.key();
+        let royalty_bps = self.accessory_record.royalty_bps_for(&seller);
+
+        let split = SaleSplit::new(self.offer.amount, self.config.fee, royalty_bps)?;
+
+        let (creator_amounts, seller_amount) =
+            self.accessory_record.creator_payouts(&seller, &split)?;
+
+        let offer = self.offer.to_account_info();
+
+        // 1. Collect fees
+        transfer_lamports(&offer, &self.treasury.to_account_info(), split.fee)?;
+
+        // 2. Pay creators their share of the sale or royalty
+        for ((creator, amount), creator_info) in self
+            .accessory_record
+            .creators
+            .iter()
+            .zip(creator_amounts)
+            .zip(creator_accounts)
+        {
+            require_keys_eq!(
+                creator_info.key(),
+                creator.address,
+                ShapelyError::InvalidCreator
+            );
+
+            if amount == 0 {
+                continue;
+            }
+
+            transfer_lamports(&offer, creator_info, amount)?;
+        }
+
+        // 3. Payout seller
+        transfer_lamports(&offer, &self.seller.to_account_info(), seller_amount)?;
+
+        Ok(())
+    }
+
+    pub fn deliver_nft(&mut self) -> Result<()> {
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked {
+            from: self.seller_accessory_ata.to_account_info(),
+            mint: self.accessory_mint.to_account_info(),
+            to: self.buyer_accessory_ata.to_account_info(),
+            authority: self.seller.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        transfer_checked(cpi_ctx, 1, self.accessory_mint.decimals)?;
+
+        Ok(())
+    }
+
+    pub fn lock_metadata(&mut self) -> Result<()> {
+        self.accessory_record.metadata_locked = true;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    metadata::{Metadata, MetadataAccount},
+    token::Mint,
+};
+
+use crate::{
+    constants::MAX_ROYALTY_BPS,
+    error::ShapelyError,
+    state::{AccessoryRecord, Config, CreatorShare},
+};
+
+#[derive(Accounts)]
+pub struct BackfillAccessoryRecord<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Artist credited with the accessory, legacy mints don't record them on-chain
+    pub artist: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+        has_one = admin @ ShapelyError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    pub accessory_mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [
+            b"metadata".as_ref(),
+            metadata_program.key().as_ref(),
+            accessory_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub accessory_metadata: Account<'info, MetadataAccount>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"accessory", accessory_mint.key().as_ref()],
+        bump,
+        space = AccessoryRecord::SPACE
+    )]
+    pub accessory_record: Account<'info, AccessoryRecord>,
+
+    pub metadata_program: Program<'info, Metadata>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BackfillAccessoryRecord<'info> {
+    /// Creates the record of an accessory minted before records existed
+    pub fn backfill_accessory_record(
+        &mut self,
+        royalty_bps: u16,
+        slot: u8,
+        bumps: &BackfillAccessoryRecordBumps,
+    ) -> Result<()> {
+        require!(royalty_bps <= MAX_ROYALTY_BPS, ShapelyError::RoyaltyTooHigh);
+
+        self.config
+            .verify_accessory(&self.accessory_metadata, &self.accessory_mint.key())?;
+
+        self.accessory_record.set_inner(AccessoryRecord {
+            bump: bumps.accessory_record,
+            accessory_mint: self.accessory_mint.key(),
+            artist: self.artist.key(),
+            slot,
+            royalty_bps,
+            metadata_locked: false,
+            creators: vec![CreatorShare {
+                address: self.artist.key(),
+                share: 100,
+            }],
+        });
+
+        Ok(())
+    }
+}
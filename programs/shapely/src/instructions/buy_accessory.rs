@@ -3,8 +3,13 @@ use anchor_lang::{
     system_program::{transfer, Transfer},
 };
 use anchor_spl::{
-    associated_token::AssociatedToken,
-    metadata::{Metadata, MetadataAccount},
+    associated_token::{create_idempotent, AssociatedToken, Create},
+    metadata::{
+        mpl_token_metadata::instructions::{
+            ThawDelegatedAccountCpi, ThawDelegatedAccountCpiAccounts,
+        },
+        MasterEditionAccount, Metadata, MetadataAccount,
+    },
     token::{
         close_account, transfer_checked, CloseAccount, Mint, Token, TokenAccount, TransferChecked,
     },
@@ -12,7 +17,9 @@ use anchor_spl::{
 
 use crate::{
     error::ShapelyError,
-    state::{Config, Listing},
+    events::ReferralFeePaid,
+    payment::SaleSplit,
+    state::{AccessoryRecord, Config, Listing, Proceeds},
 };
 
 #[derive(Accounts)]
@@ -20,33 +27,32 @@ pub struct BuyAccessory<'info> {
     #[account(mut)]
     pub collector: Signer<'info>,
 
+    /// The avatar accounts are only needed when the config avatar policy requires it
     #[account(
         seeds = [b"avatar", collector.key().as_ref(), config.avatar_collection.key().as_ref()],
         bump,
     )]
-    pub collector_avatar_mint: Account<'info, Mint>,
+    pub collector_avatar_mint: Option<Box<Account<'info, Mint>>>,
 
-    #[account(
-        seeds = [
-            b"metadata".as_ref(),
-            metadata_program.key().as_ref(),
-            collector_avatar_mint.key().as_ref(),
-        ],
-        bump,
-        seeds::program = metadata_program.key(),
-
-        constraint = collector_avatar_metadata.collection.as_ref().unwrap().verified == true @ ShapelyError::AvatarNotVerified,
-        constraint = collector_avatar_metadata.collection.as_ref().unwrap().key.as_ref() ==
-        config.avatar_collection.key().as_ref() @ ShapelyError::AvatarNotVerified
-    )]
-    pub collector_avatar_metadata: Account<'info, MetadataAccount>,
+    /// Validated against the avatar mint and collection in the handler
+    pub collector_avatar_metadata: Option<Box<Account<'info, MetadataAccount>>>,
 
-    #[account(mut)]
     /// CHECK: This will be validated in the listing seeds
     pub artist: AccountInfo<'info>,
 
+    /// The seller's wallet as a writable account, only needed when the listing pays them directly
+    #[account(mut, address = artist.key())]
+    pub artist_payout: Option<SystemAccount<'info>>,
+
     pub accessory_mint: Account<'info, Mint>,
 
+    #[account(
+        mut,
+        seeds = [b"accessory", accessory_mint.key().as_ref()],
+        bump = accessory_record.bump
+    )]
+    pub accessory_record: Account<'info, AccessoryRecord>,
+
     #[account(
         init_if_needed,
         payer = collector,
@@ -57,18 +63,38 @@ pub struct BuyAccessory<'info> {
 
     #[account(
         mut,
-        close = artist,
         seeds = [b"listing", accessory_mint.key().as_ref(), artist.key().as_ref()],
         bump = listing.bump
     )]
     pub listing: Account<'info, Listing>,
 
+    /// Only needed when the accessory is escrowed in a listing vault
     #[account(
         mut,
         associated_token::mint = accessory_mint,
         associated_token::authority = listing
     )]
-    pub listing_vault: Account<'info, TokenAccount>,
+    pub listing_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// The accounts below are only needed when the listing is delegated
+    #[account(
+        mut,
+        associated_token::mint = accessory_mint,
+        associated_token::authority = artist
+    )]
+    pub artist_accessory_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        seeds = [
+            b"metadata".as_ref(),
+            metadata_program.key().as_ref(),
+            accessory_mint.key().as_ref(),
+            b"edition".as_ref(),
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub accessory_master_edition: Option<Box<Account<'info, MasterEditionAccount>>>,
 
     #[account(seeds = [b"config", config.seed.to_le_bytes().as_ref()], bump = config.bump)]
     pub config: Account<'info, Config>,
@@ -76,6 +102,58 @@ pub struct BuyAccessory<'info> {
     #[account(mut, seeds = [b"treasury", config.key().as_ref()], bump = config.treasury_bump)]
     pub treasury: SystemAccount<'info>,
 
+    /// The accounts below are only needed when the listing is priced in an SPL token
+    pub payment_mint: Option<Box<Account<'info, Mint>>>,
+
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::authority = collector
+    )]
+    pub collector_payment_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = collector,
+        associated_token::mint = payment_mint,
+        associated_token::authority = artist
+    )]
+    pub artist_payment_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = collector,
+        associated_token::mint = payment_mint,
+        associated_token::authority = treasury
+    )]
+    pub treasury_payment_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Only needed when the listing credits the seller's proceeds account
+    #[account(
+        mut,
+        seeds = [b"proceeds", artist.key().as_ref()],
+        bump = seller_proceeds.bump
+    )]
+    pub seller_proceeds: Option<Box<Account<'info, Proceeds>>>,
+
+    /// The frontend that referred the buyer, paid a share of the treasury fee
+    #[account(
+        mut,
+        constraint = config.referrers.contains(&referrer.key()) @ ShapelyError::InvalidReferrer,
+        constraint = referrer.key() != collector.key() && referrer.key() != artist.key()
+            @ ShapelyError::InvalidReferrer
+    )]
+    pub referrer: Option<SystemAccount<'info>>,
+
+    /// Only needed alongside a referrer when the listing is priced in an SPL token
+    #[account(
+        init_if_needed,
+        payer = collector,
+        associated_token::mint = payment_mint,
+        associated_token::authority = referrer
+    )]
+    pub referrer_payment_ata: Option<Box<Account<'info, TokenAccount>>>,
+
     pub token_program: Program<'info, Token>,
 
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -86,7 +164,14 @@ pub struct BuyAccessory<'info> {
 }
 
 impl<'info> BuyAccessory<'info> {
-    pub fn buy_accessory(&mut self) -> Result<()> {
+    /// Remaining accounts hold one group per creator in the accessory record, in order:
+    /// `[creator (mut)]` for SOL listings or `[creator, creator_payment_ata (mut)]` for SPL listings
+    pub fn buy_accessory(
+        &mut self,
+        expected_price: u64,
+        expected_payment_mint: Option<Pubkey>,
+        creator_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
         let seeds = &[
             b"listing",
             self.accessory_mint.to_account_info().key.as_ref(),
@@ -95,15 +180,150 @@ impl<'info> BuyAccessory<'info> {
         ];
         let signer_seeds = &[&seeds[..]];
 
-        self.process_payment()?;
-        self.withdraw_nft(signer_seeds)?;
-        self.close_vault(signer_seeds)?;
+        require!(
+            self.listing.can_be_bought_by(&self.collector.key()),
+            ShapelyError::ReservedForAnotherBuyer
+        );
+
+        self.verify_collector_avatar()?;
+
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(!self.listing.is_expired(now), ShapelyError::ListingExpired);
+
+        let price = self.listing.price_at(now)?;
+
+        // Protects the buyer against the listing being repriced before the purchase lands,
+        // a dutch auction can only get cheaper so the expected price is a maximum there
+        if self.listing.dutch_auction.is_some() {
+            require!(price <= expected_price, ShapelyError::UnexpectedPrice);
+        } else {
+            require!(price == expected_price, ShapelyError::UnexpectedPrice);
+        }
+        require!(
+            self.listing.payment_mint == expected_payment_mint,
+            ShapelyError::UnexpectedPaymentMint
+        );
+
+        self.process_payment(price, creator_accounts)?;
+        if self.listing.delegated {
+            self.thaw_nft(signer_seeds)?;
+            self.transfer_delegated_nft(signer_seeds)?;
+        } else {
+            self.withdraw_nft(signer_seeds)?;
+        }
+        self.refund_rent(signer_seeds)?;
+        self.lock_metadata()?;
+        Ok(())
+    }
+
+    /// The account receiving the seller's SOL, their proceeds account when they claim in batches
+    pub fn seller_receiver(&self) -> Result<AccountInfo<'info>> {
+        if self.listing.claimable_proceeds {
+            self.seller_proceeds
+                .as_ref()
+                .map(|seller_proceeds| seller_proceeds.to_account_info())
+                .ok_or(ShapelyError::MissingSellerProceeds.into())
+        } else {
+            self.artist_payout
+                .as_ref()
+                .map(|artist_payout| artist_payout.to_account_info())
+                .ok_or(ShapelyError::MissingSellerPayout.into())
+        }
+    }
+
+    /// Returns the listing and vault rent to the seller, credited to their proceeds in pull mode
+    pub fn refund_rent(&mut self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let receiver = self.seller_receiver()?;
+        let lamports_before = receiver.lamports();
+
+        if !self.listing.delegated {
+            self.close_vault(&receiver, signer_seeds)?;
+        }
+        self.listing.close(receiver.clone())?;
+
+        if let Some(seller_proceeds) = self
+            .seller_proceeds
+            .as_mut()
+            .filter(|_| self.listing.claimable_proceeds)
+        {
+            let refunded = receiver
+                .lamports()
+                .checked_sub(lamports_before)
+                .ok_or(ShapelyError::MathOverflow)?;
+
+            seller_proceeds.refund_rent(refunded)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn verify_collector_avatar(&self) -> Result<()> {
+        let avatar_mint = self.collector_avatar_mint.as_ref().map(|mint| mint.key());
+        let avatar = self
+            .collector_avatar_metadata
+            .as_deref()
+            .zip(avatar_mint.as_ref())
+            .map(|(metadata, mint)| (&**metadata, mint));
+        let primary_sale = self.artist.key() == self.accessory_record.artist;
+
+        self.config.verify_buyer_avatar(avatar, primary_sale)
+    }
+
+    pub fn process_payment(
+        &mut self,
+        price: u64,
+        creator_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        let seller = self.artist.key();
+        let royalty_bps = self.accessory_record.royalty_bps_for(&seller);
+
+        let mut split = SaleSplit::new(price, self.config.fee, royalty_bps)?;
+
+        if self.referrer.is_some() {
+            split = split.with_referral(self.config.referral_bps)?;
+        }
+
+        let (creator_amounts, seller_amount) =
+            self.accessory_record.creator_payouts(&seller, &split)?;
+
+        match self.listing.payment_mint {
+            Some(payment_mint) => self.pay_in_tokens(
+                payment_mint,
+                &split,
+                &creator_amounts,
+                seller_amount,
+                creator_accounts,
+            )?,
+            None => self.pay_in_sol(&split, &creator_amounts, seller_amount, creator_accounts)?,
+        }
+
+        if let Some(referrer) = self.referrer.as_ref() {
+            emit!(ReferralFeePaid {
+                listing: self.listing.key(),
+                accessory_mint: self.accessory_mint.key(),
+                buyer: self.collector.key(),
+                referrer: referrer.key(),
+                payment_mint: self.listing.payment_mint,
+                amount: split.referral,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+
         Ok(())
     }
 
-    pub fn process_payment(&mut self) -> Result<()> {
-        let listing_fee = ((self.config.fee / 10_000) as u64) * self.listing.price;
-        let amount_to_pay_artist = self.listing.price - listing_fee;
+    pub fn pay_in_sol(
+        &mut self,
+        split: &SaleSplit,
+        creator_amounts: &[u64],
+        seller_amount: u64,
+        creator_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        require!(
+            creator_accounts.len() == self.accessory_record.creators.len(),
+            ShapelyError::InvalidCreator
+        );
 
         // 1. Collect fees
         let cpi_accounts = Transfer {
@@ -113,26 +333,198 @@ impl<'info> BuyAccessory<'info> {
 
         let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
 
-        transfer(cpi_ctx, listing_fee)?;
+        transfer(cpi_ctx, split.fee)?;
 
-        // 2. Payout artist
-        let cpi_accounts = Transfer {
-            from: self.collector.to_account_info(),
-            to: self.artist.to_account_info(),
+        // 2. Pay referrer
+        if let Some(referrer) = self.referrer.as_ref().filter(|_| split.referral > 0) {
+            let cpi_accounts = Transfer {
+                from: self.collector.to_account_info(),
+                to: referrer.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+            transfer(cpi_ctx, split.referral)?;
+        }
+
+        // 3. Pay creators their share of the sale or royalty
+        for ((creator, amount), creator_info) in self
+            .accessory_record
+            .creators
+            .iter()
+            .zip(creator_amounts)
+            .zip(creator_accounts)
+        {
+            require_keys_eq!(
+                creator_info.key(),
+                creator.address,
+                ShapelyError::InvalidCreator
+            );
+
+            if *amount == 0 {
+                continue;
+            }
+
+            let cpi_accounts = Transfer {
+                from: self.collector.to_account_info(),
+                to: creator_info.clone(),
+            };
+
+            let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+            transfer(cpi_ctx, *amount)?;
+        }
+
+        // 4. Payout seller, or credit their proceeds when they claim in batches
+        if self.listing.claimable_proceeds {
+            let seller_proceeds = self
+                .seller_proceeds
+                .as_mut()
+                .ok_or(ShapelyError::MissingSellerProceeds)?;
+
+            let cpi_accounts = Transfer {
+                from: self.collector.to_account_info(),
+                to: seller_proceeds.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+            transfer(cpi_ctx, seller_amount)?;
+
+            seller_proceeds.credit(seller_amount)?;
+        } else {
+            let cpi_accounts = Transfer {
+                from: self.collector.to_account_info(),
+                to: self.seller_receiver()?,
+            };
+
+            let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+            transfer(cpi_ctx, seller_amount)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn pay_in_tokens(
+        &mut self,
+        payment_mint: Pubkey,
+        split: &SaleSplit,
+        creator_amounts: &[u64],
+        seller_amount: u64,
+        creator_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        let (
+            Some(mint),
+            Some(collector_payment_ata),
+            Some(artist_payment_ata),
+            Some(treasury_payment_ata),
+        ) = (
+            self.payment_mint.as_ref(),
+            self.collector_payment_ata.as_ref(),
+            self.artist_payment_ata.as_ref(),
+            self.treasury_payment_ata.as_ref(),
+        )
+        else {
+            return err!(ShapelyError::MissingPaymentAccounts);
         };
 
-        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+        require_keys_eq!(mint.key(), payment_mint, ShapelyError::InvalidPaymentMint);
+        require!(
+            creator_accounts.len() == self.accessory_record.creators.len() * 2,
+            ShapelyError::InvalidCreator
+        );
+
+        let referrer_payment_ata = self.referrer_payment_ata.as_ref();
 
-        transfer(cpi_ctx, amount_to_pay_artist)?;
+        require!(
+            self.referrer.is_none() || referrer_payment_ata.is_some(),
+            ShapelyError::MissingPaymentAccounts
+        );
+
+        let mut payouts = vec![
+            // 1. Collect fees
+            (treasury_payment_ata.to_account_info(), split.fee),
+        ];
+
+        // 2. Pay referrer
+        if let Some(referrer_payment_ata) = referrer_payment_ata {
+            payouts.push((referrer_payment_ata.to_account_info(), split.referral));
+        }
+
+        // 3. Pay creators their share of the sale or royalty
+        for ((creator, amount), accounts) in self
+            .accessory_record
+            .creators
+            .iter()
+            .zip(creator_amounts)
+            .zip(creator_accounts.chunks(2))
+        {
+            let [creator_info, creator_payment_ata] = accounts else {
+                return err!(ShapelyError::InvalidCreator);
+            };
+
+            require_keys_eq!(
+                creator_info.key(),
+                creator.address,
+                ShapelyError::InvalidCreator
+            );
+
+            if *amount == 0 {
+                continue;
+            }
+
+            let cpi_accounts = Create {
+                payer: self.collector.to_account_info(),
+                associated_token: creator_payment_ata.clone(),
+                authority: creator_info.clone(),
+                mint: mint.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new(
+                self.associated_token_program.to_account_info(),
+                cpi_accounts,
+            );
+
+            create_idempotent(cpi_ctx)?;
+
+            payouts.push((creator_payment_ata.clone(), *amount));
+        }
+
+        // 4. Payout seller
+        payouts.push((artist_payment_ata.to_account_info(), seller_amount));
+
+        for (destination, amount) in payouts {
+            if amount == 0 {
+                continue;
+            }
+
+            let cpi_accounts = TransferChecked {
+                from: collector_payment_ata.to_account_info(),
+                mint: mint.to_account_info(),
+                to: destination,
+                authority: self.collector.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+
+            transfer_checked(cpi_ctx, amount, mint.decimals)?;
+        }
 
         Ok(())
     }
 
     pub fn withdraw_nft(&mut self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let Some(listing_vault) = self.listing_vault.as_ref() else {
+            return err!(ShapelyError::InvalidListingAccounts);
+        };
+
         let cpi_program = self.token_program.to_account_info();
 
         let cpi_accounts = TransferChecked {
-            from: self.listing_vault.to_account_info(),
+            from: listing_vault.to_account_info(),
             mint: self.accessory_mint.to_account_info(),
             to: self.collector_accessory_ata.to_account_info(),
             authority: self.listing.to_account_info(),
@@ -140,21 +532,25 @@ impl<'info> BuyAccessory<'info> {
 
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
 
-        transfer_checked(
-            cpi_ctx,
-            self.listing_vault.amount,
-            self.accessory_mint.decimals,
-        )?;
+        transfer_checked(cpi_ctx, listing_vault.amount, self.accessory_mint.decimals)?;
 
         Ok(())
     }
 
-    pub fn close_vault(&mut self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+    pub fn close_vault(
+        &self,
+        destination: &AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let Some(listing_vault) = self.listing_vault.as_ref() else {
+            return err!(ShapelyError::InvalidListingAccounts);
+        };
+
         let cpi_program = self.token_program.to_account_info();
 
         let cpi_accounts = CloseAccount {
-            account: self.listing_vault.to_account_info(),
-            destination: self.artist.to_account_info(),
+            account: listing_vault.to_account_info(),
+            destination: destination.clone(),
             authority: self.listing.to_account_info(),
         };
 
@@ -164,4 +560,66 @@ impl<'info> BuyAccessory<'info> {
 
         Ok(())
     }
+
+    pub fn thaw_nft(&mut self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let (Some(artist_accessory_ata), Some(accessory_master_edition)) = (
+            self.artist_accessory_ata.as_ref(),
+            self.accessory_master_edition.as_ref(),
+        ) else {
+            return err!(ShapelyError::InvalidListingAccounts);
+        };
+
+        let metadata_program = &self.metadata_program.to_account_info();
+        let listing = &self.listing.to_account_info();
+        let artist_accessory_ata = &artist_accessory_ata.to_account_info();
+        let accessory_master_edition = &accessory_master_edition.to_account_info();
+        let accessory_mint = &self.accessory_mint.to_account_info();
+        let token_program = &self.token_program.to_account_info();
+
+        ThawDelegatedAccountCpi::new(
+            metadata_program,
+            ThawDelegatedAccountCpiAccounts {
+                delegate: listing,
+                token_account: artist_accessory_ata,
+                edition: accessory_master_edition,
+                mint: accessory_mint,
+                token_program,
+            },
+        )
+        .invoke_signed(signer_seeds)?;
+
+        Ok(())
+    }
+
+    /// Moves the accessory straight out of the seller's wallet using the listing's delegation
+    pub fn transfer_delegated_nft(&mut self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let Some(artist_accessory_ata) = self.artist_accessory_ata.as_ref() else {
+            return err!(ShapelyError::InvalidListingAccounts);
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked {
+            from: artist_accessory_ata.to_account_info(),
+            mint: self.accessory_mint.to_account_info(),
+            to: self.collector_accessory_ata.to_account_info(),
+            authority: self.listing.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        transfer_checked(
+            cpi_ctx,
+            artist_accessory_ata.delegated_amount,
+            self.accessory_mint.decimals,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn lock_metadata(&mut self) -> Result<()> {
+        self.accessory_record.metadata_locked = true;
+
+        Ok(())
+    }
 }
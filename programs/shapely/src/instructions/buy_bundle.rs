@@ -0,0 +1,301 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::{
+    associated_token::{create_idempotent, get_associated_token_address, AssociatedToken, Create},
+    metadata::{Metadata, MetadataAccount},
+    token::{
+        close_account, transfer_checked, CloseAccount, Mint, Token, TokenAccount, TransferChecked,
+    },
+};
+
+use crate::{
+    error::ShapelyError,
+    payment::SaleSplit,
+    state::{AccessoryRecord, BundleListing, Config},
+};
+
+#[derive(Accounts)]
+pub struct BuyBundle<'info> {
+    #[account(mut)]
+    pub collector: Signer<'info>,
+
+    /// The avatar accounts are only needed when the config avatar policy requires it
+    #[account(
+        seeds = [b"avatar", collector.key().as_ref(), config.avatar_collection.key().as_ref()],
+        bump,
+    )]
+    pub collector_avatar_mint: Option<Account<'info, Mint>>,
+
+    /// Validated against the avatar mint and collection in the handler
+    pub collector_avatar_metadata: Option<Account<'info, MetadataAccount>>,
+
+    #[account(mut)]
+    /// CHECK: This will be validated in the bundle seeds
+    pub seller: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [b"bundle", seller.key().as_ref(), bundle.id.to_le_bytes().as_ref()],
+        bump = bundle.bump
+    )]
+    pub bundle: Account<'info, BundleListing>,
+
+    #[account(seeds = [b"config", config.seed.to_le_bytes().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"treasury", config.key().as_ref()], bump = config.treasury_bump)]
+    pub treasury: SystemAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub metadata_program: Program<'info, Metadata>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BuyBundle<'info> {
+    /// Remaining accounts hold one group per accessory, in the bundle order:
+    /// `[accessory_mint, accessory_record (mut), bundle_vault (mut), collector_accessory_ata (mut)]`
+    /// followed by one `[creator (mut)]` per creator of each accessory, in the same order
+    pub fn buy_bundle(
+        &mut self,
+        expected_price: u64,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        require!(
+            self.bundle.price == expected_price,
+            ShapelyError::UnexpectedPrice
+        );
+        require!(
+            remaining_accounts.len() >= self.bundle.accessory_mints.len() * 4,
+            ShapelyError::InvalidBundleAccounts
+        );
+
+        let (accessory_accounts, creator_accounts) =
+            remaining_accounts.split_at(self.bundle.accessory_mints.len() * 4);
+
+        let seller = self.seller.key();
+        let id_bytes = self.bundle.id.to_le_bytes();
+        let seeds = &[
+            b"bundle",
+            seller.as_ref(),
+            id_bytes.as_ref(),
+            &[self.bundle.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        // Bundles are always sold by the artist who minted them
+        self.verify_collector_avatar(true)?;
+
+        self.process_payment(accessory_accounts, creator_accounts)?;
+
+        for (index, accounts) in accessory_accounts.chunks(4).enumerate() {
+            self.withdraw_accessory(index, accounts, signer_seeds)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn verify_collector_avatar(&self, primary_sale: bool) -> Result<()> {
+        let avatar_mint = self.collector_avatar_mint.as_ref().map(|mint| mint.key());
+        let avatar = self
+            .collector_avatar_metadata
+            .as_ref()
+            .zip(avatar_mint.as_ref())
+            .map(|(metadata, mint)| (&**metadata, mint));
+
+        self.config.verify_buyer_avatar(avatar, primary_sale)
+    }
+
+    /// The seller minted every accessory in the bundle, so no royalty is taken. What is left
+    /// after the fee is spread evenly over the accessories and split between their creators
+    pub fn process_payment(
+        &mut self,
+        accessory_accounts: &'info [AccountInfo<'info>],
+        creator_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        let seller = self.seller.key();
+        let split = SaleSplit::new(self.bundle.price, self.config.fee, 0)?;
+
+        let accessory_count = self.bundle.accessory_mints.len() as u64;
+        let accessory_share = split.seller / accessory_count;
+        let mut remainder = split.seller - accessory_share * accessory_count;
+
+        // 1. Collect fees
+        let cpi_accounts = Transfer {
+            from: self.collector.to_account_info(),
+            to: self.treasury.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+        transfer(cpi_ctx, split.fee)?;
+
+        // 2. Pay creators their share of each accessory
+        let mut creator_accounts = creator_accounts;
+        let mut seller_total = 0u64;
+
+        for (index, accounts) in accessory_accounts.chunks(4).enumerate() {
+            let accessory_record = Account::<AccessoryRecord>::try_from(&accounts[1])?;
+
+            // Records are only ever created at the PDA of the mint they store
+            require_keys_eq!(
+                accessory_record.accessory_mint,
+                self.bundle.accessory_mints[index],
+                ShapelyError::InvalidBundleAccounts
+            );
+
+            // Rounding dust goes to the first accessory
+            let accessory_split = SaleSplit {
+                fee: 0,
+                referral: 0,
+                royalty: 0,
+                seller: accessory_share + std::mem::take(&mut remainder),
+            };
+
+            let (creator_amounts, seller_amount) =
+                accessory_record.creator_payouts(&seller, &accessory_split)?;
+
+            let creator_count = accessory_record.creators.len();
+            require!(
+                creator_accounts.len() >= creator_count,
+                ShapelyError::InvalidCreator
+            );
+            let (accessory_creators, rest) = creator_accounts.split_at(creator_count);
+            creator_accounts = rest;
+
+            for ((creator, amount), creator_info) in accessory_record
+                .creators
+                .iter()
+                .zip(creator_amounts)
+                .zip(accessory_creators)
+            {
+                require_keys_eq!(
+                    creator_info.key(),
+                    creator.address,
+                    ShapelyError::InvalidCreator
+                );
+
+                if amount == 0 {
+                    continue;
+                }
+
+                let cpi_accounts = Transfer {
+                    from: self.collector.to_account_info(),
+                    to: creator_info.clone(),
+                };
+
+                let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+                transfer(cpi_ctx, amount)?;
+            }
+
+            seller_total = seller_total
+                .checked_add(seller_amount)
+                .ok_or(ShapelyError::MathOverflow)?;
+        }
+
+        require!(creator_accounts.is_empty(), ShapelyError::InvalidCreator);
+
+        // 3. Payout seller
+        let cpi_accounts = Transfer {
+            from: self.collector.to_account_info(),
+            to: self.seller.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+        transfer(cpi_ctx, seller_total)?;
+
+        Ok(())
+    }
+
+    pub fn withdraw_accessory(
+        &mut self,
+        index: usize,
+        accounts: &'info [AccountInfo<'info>],
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let [accessory_mint_info, accessory_record_info, bundle_vault_info, collector_ata_info] =
+            accounts
+        else {
+            return err!(ShapelyError::InvalidBundleAccounts);
+        };
+
+        let accessory_mint = Account::<Mint>::try_from(accessory_mint_info)?;
+        let mut accessory_record = Account::<AccessoryRecord>::try_from(accessory_record_info)?;
+        let bundle_vault = Account::<TokenAccount>::try_from(bundle_vault_info)?;
+
+        require_keys_eq!(
+            accessory_mint.key(),
+            self.bundle.accessory_mints[index],
+            ShapelyError::InvalidBundleAccounts
+        );
+        // Records are only ever created at the PDA of the mint they store
+        require_keys_eq!(
+            accessory_record.accessory_mint,
+            accessory_mint.key(),
+            ShapelyError::InvalidBundleAccounts
+        );
+        require_keys_eq!(
+            bundle_vault.key(),
+            get_associated_token_address(&self.bundle.key(), &accessory_mint.key()),
+            ShapelyError::InvalidBundleAccounts
+        );
+
+        let cpi_accounts = Create {
+            payer: self.collector.to_account_info(),
+            associated_token: collector_ata_info.clone(),
+            authority: self.collector.to_account_info(),
+            mint: accessory_mint_info.clone(),
+            system_program: self.system_program.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            cpi_accounts,
+        );
+
+        create_idempotent(cpi_ctx)?;
+
+        let cpi_accounts = TransferChecked {
+            from: bundle_vault_info.clone(),
+            mint: accessory_mint_info.clone(),
+            to: collector_ata_info.clone(),
+            authority: self.bundle.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        transfer_checked(cpi_ctx, bundle_vault.amount, accessory_mint.decimals)?;
+
+        let cpi_accounts = CloseAccount {
+            account: bundle_vault_info.clone(),
+            destination: self.seller.to_account_info(),
+            authority: self.bundle.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        close_account(cpi_ctx)?;
+
+        accessory_record.metadata_locked = true;
+        accessory_record.exit(&crate::ID)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,278 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::{
+    associated_token::{create_idempotent, get_associated_token_address, AssociatedToken, Create},
+    metadata::MetadataAccount,
+    token::{
+        close_account, transfer_checked, CloseAccount, Mint, Token, TokenAccount, TransferChecked,
+    },
+};
+
+use crate::{
+    error::ShapelyError,
+    payment::SaleSplit,
+    state::{AccessoryRecord, Config, Listing},
+};
+
+#[derive(Accounts)]
+pub struct BuyMany<'info> {
+    #[account(mut)]
+    pub collector: Signer<'info>,
+
+    /// The avatar accounts are only needed when the config avatar policy requires it
+    #[account(
+        seeds = [b"avatar", collector.key().as_ref(), config.avatar_collection.key().as_ref()],
+        bump,
+    )]
+    pub collector_avatar_mint: Option<Account<'info, Mint>>,
+
+    /// Validated against the avatar mint and collection in the handler
+    pub collector_avatar_metadata: Option<Account<'info, MetadataAccount>>,
+
+    #[account(seeds = [b"config", config.seed.to_le_bytes().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"treasury", config.key().as_ref()], bump = config.treasury_bump)]
+    pub treasury: SystemAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BuyMany<'info> {
+    /// Listings in a cart must be priced in SOL and escrowed in a listing vault.
+    /// Remaining accounts hold one group per listing:
+    /// `[listing (mut), listing_vault (mut), accessory_mint, collector_accessory_ata (mut),
+    /// seller (mut), accessory_record (mut)]` followed by one `[creator (mut)]` per creator
+    /// in the accessory record, in order.
+    pub fn buy_many(
+        &mut self,
+        max_total_price: u64,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        let mut total_price: u64 = 0;
+        let mut total_fee: u64 = 0;
+        let mut primary_sale = false;
+        let mut accounts = remaining_accounts;
+
+        require!(!accounts.is_empty(), ShapelyError::InvalidCartAccounts);
+
+        while !accounts.is_empty() {
+            let purchase = self.buy_listing(accounts, now)?;
+
+            total_price = total_price
+                .checked_add(purchase.price)
+                .ok_or(ShapelyError::MathOverflow)?;
+            total_fee = total_fee
+                .checked_add(purchase.fee)
+                .ok_or(ShapelyError::MathOverflow)?;
+            primary_sale |= purchase.primary_sale;
+            accounts = &accounts[purchase.accounts_used..];
+        }
+
+        // Protects the buyer against listings being repriced before the purchase lands
+        require!(
+            total_price <= max_total_price,
+            ShapelyError::UnexpectedPrice
+        );
+
+        self.verify_collector_avatar(primary_sale)?;
+        self.collect_fees(total_fee)
+    }
+
+    pub fn buy_listing(
+        &mut self,
+        accounts: &'info [AccountInfo<'info>],
+        now: i64,
+    ) -> Result<CartPurchase> {
+        let [listing_info, listing_vault_info, accessory_mint_info, collector_ata_info, seller_info, accessory_record_info, creator_accounts @ ..] =
+            accounts
+        else {
+            return err!(ShapelyError::InvalidCartAccounts);
+        };
+
+        let listing = Account::<Listing>::try_from(listing_info)?;
+        let accessory_mint = Account::<Mint>::try_from(accessory_mint_info)?;
+        let listing_vault = Account::<TokenAccount>::try_from(listing_vault_info)?;
+        let mut accessory_record = Account::<AccessoryRecord>::try_from(accessory_record_info)?;
+
+        let listing_address = Pubkey::create_program_address(
+            &[
+                b"listing",
+                accessory_mint.key().as_ref(),
+                seller_info.key.as_ref(),
+                &[listing.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| ShapelyError::InvalidCartAccounts)?;
+
+        require_keys_eq!(
+            listing.key(),
+            listing_address,
+            ShapelyError::InvalidCartAccounts
+        );
+        require_keys_eq!(
+            listing_vault.key(),
+            get_associated_token_address(&listing.key(), &accessory_mint.key()),
+            ShapelyError::InvalidCartAccounts
+        );
+        // Records are only ever created at the PDA of the mint they store
+        require_keys_eq!(
+            accessory_record.accessory_mint,
+            accessory_mint.key(),
+            ShapelyError::InvalidCartAccounts
+        );
+        require!(
+            listing.payment_mint.is_none() && !listing.delegated && !listing.claimable_proceeds,
+            ShapelyError::UnsupportedCartListing
+        );
+        require!(!listing.is_expired(now), ShapelyError::ListingExpired);
+        require!(
+            listing.can_be_bought_by(&self.collector.key()),
+            ShapelyError::ReservedForAnotherBuyer
+        );
+
+        let creator_count = accessory_record.creators.len();
+        let creator_accounts = creator_accounts
+            .get(..creator_count)
+            .ok_or(ShapelyError::InvalidCartAccounts)?;
+
+        let price = listing.price_at(now)?;
+        let royalty_bps = accessory_record.royalty_bps_for(seller_info.key);
+        let split = SaleSplit::new(price, self.config.fee, royalty_bps)?;
+        let (creator_amounts, seller_amount) =
+            accessory_record.creator_payouts(seller_info.key, &split)?;
+
+        // 1. Pay creators their share of the sale or royalty
+        for ((creator, amount), creator_info) in accessory_record
+            .creators
+            .iter()
+            .zip(creator_amounts)
+            .zip(creator_accounts)
+        {
+            require_keys_eq!(
+                creator_info.key(),
+                creator.address,
+                ShapelyError::InvalidCreator
+            );
+
+            self.pay_sol(creator_info, amount)?;
+        }
+
+        // 2. Payout seller
+        self.pay_sol(seller_info, seller_amount)?;
+
+        let accessory_mint_key = accessory_mint.key();
+        let seeds = &[
+            b"listing",
+            accessory_mint_key.as_ref(),
+            seller_info.key.as_ref(),
+            &[listing.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = Create {
+            payer: self.collector.to_account_info(),
+            associated_token: collector_ata_info.clone(),
+            authority: self.collector.to_account_info(),
+            mint: accessory_mint_info.clone(),
+            system_program: self.system_program.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            cpi_accounts,
+        );
+
+        create_idempotent(cpi_ctx)?;
+
+        let cpi_accounts = TransferChecked {
+            from: listing_vault_info.clone(),
+            mint: accessory_mint_info.clone(),
+            to: collector_ata_info.clone(),
+            authority: listing_info.clone(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        transfer_checked(cpi_ctx, listing_vault.amount, accessory_mint.decimals)?;
+
+        let cpi_accounts = CloseAccount {
+            account: listing_vault_info.clone(),
+            destination: seller_info.clone(),
+            authority: listing_info.clone(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        close_account(cpi_ctx)?;
+
+        listing.close(seller_info.clone())?;
+
+        accessory_record.metadata_locked = true;
+        accessory_record.exit(&crate::ID)?;
+
+        Ok(CartPurchase {
+            price,
+            fee: split.fee,
+            primary_sale: *seller_info.key == accessory_record.artist,
+            accounts_used: 6 + creator_count,
+        })
+    }
+
+    pub fn verify_collector_avatar(&self, primary_sale: bool) -> Result<()> {
+        let avatar_mint = self.collector_avatar_mint.as_ref().map(|mint| mint.key());
+        let avatar = self
+            .collector_avatar_metadata
+            .as_ref()
+            .zip(avatar_mint.as_ref())
+            .map(|(metadata, mint)| (&**metadata, mint));
+
+        self.config.verify_buyer_avatar(avatar, primary_sale)
+    }
+
+    /// Fees for the whole cart are sent to the treasury in a single transfer
+    pub fn collect_fees(&mut self, total_fee: u64) -> Result<()> {
+        self.pay_sol(&self.treasury.to_account_info(), total_fee)
+    }
+
+    pub fn pay_sol(&self, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let cpi_accounts = Transfer {
+            from: self.collector.to_account_info(),
+            to: to.clone(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+        transfer(cpi_ctx, amount)
+    }
+}
+
+/// What a single listing in a cart added up to
+pub struct CartPurchase {
+    pub price: u64,
+    pub fee: u64,
+    pub primary_sale: bool,
+    pub accounts_used: usize,
+}
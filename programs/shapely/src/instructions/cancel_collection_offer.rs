@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{CollectionOffer, Config};
+
+#[derive(Accounts)]
+pub struct CancelCollectionOffer<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// Closing the offer refunds the remaining escrow along with the rent
+    #[account(
+        mut,
+        close = buyer,
+        seeds = [
+            b"collection offer",
+            config.key().as_ref(),
+            buyer.key().as_ref(),
+            collection_offer.id.to_le_bytes().as_ref()
+        ],
+        bump = collection_offer.bump
+    )]
+    pub collection_offer: Account<'info, CollectionOffer>,
+
+    #[account(seeds = [b"config", config.seed.to_le_bytes().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CancelCollectionOffer<'info> {
+    pub fn cancel_collection_offer(&mut self) -> Result<()> {
+        msg!(
+            "Collection offer cancelled with {} accessories left",
+            self.collection_offer.remaining
+        );
+
+        Ok(())
+    }
+}
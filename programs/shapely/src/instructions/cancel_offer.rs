@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::state::Offer;
+
+#[derive(Accounts)]
+pub struct CancelOffer<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub accessory_mint: Account<'info, Mint>,
+
+    /// Closing the offer refunds the escrowed funds along with the rent
+    #[account(
+        mut,
+        close = buyer,
+        seeds = [b"offer", accessory_mint.key().as_ref(), buyer.key().as_ref()],
+        bump = offer.bump
+    )]
+    pub offer: Account<'info, Offer>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CancelOffer<'info> {
+    pub fn cancel_offer(&mut self) -> Result<()> {
+        msg!("Offer of {} lamports cancelled", self.offer.amount);
+
+        Ok(())
+    }
+}
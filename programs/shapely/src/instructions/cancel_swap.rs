@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::{create_idempotent, get_associated_token_address, AssociatedToken, Create},
+    token::{
+        close_account, transfer_checked, CloseAccount, Mint, Token, TokenAccount, TransferChecked,
+    },
+};
+
+use crate::{error::ShapelyError, state::SwapOffer};
+
+#[derive(Accounts)]
+pub struct CancelSwap<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(
+        mut,
+        close = maker,
+        seeds = [b"swap", maker.key().as_ref(), swap.id.to_le_bytes().as_ref()],
+        bump = swap.bump
+    )]
+    pub swap: Account<'info, SwapOffer>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CancelSwap<'info> {
+    /// Remaining accounts hold one group per offered accessory, in the swap order:
+    /// `[accessory_mint, swap_vault (mut), maker_accessory_ata (mut)]`
+    pub fn cancel_swap(&mut self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        require!(
+            remaining_accounts.len() == self.swap.offered_mints.len() * 3,
+            ShapelyError::InvalidSwapAccounts
+        );
+
+        let maker = self.maker.key();
+        let id_bytes = self.swap.id.to_le_bytes();
+        let seeds = &[
+            b"swap",
+            maker.as_ref(),
+            id_bytes.as_ref(),
+            &[self.swap.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        for (index, accounts) in remaining_accounts.chunks(3).enumerate() {
+            self.withdraw_accessory(index, accounts, signer_seeds)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn withdraw_accessory(
+        &mut self,
+        index: usize,
+        accounts: &'info [AccountInfo<'info>],
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let [accessory_mint_info, swap_vault_info, maker_ata_info] = accounts else {
+            return err!(ShapelyError::InvalidSwapAccounts);
+        };
+
+        let accessory_mint = Account::<Mint>::try_from(accessory_mint_info)?;
+        let swap_vault = Account::<TokenAccount>::try_from(swap_vault_info)?;
+
+        require_keys_eq!(
+            accessory_mint.key(),
+            self.swap.offered_mints[index],
+            ShapelyError::InvalidSwapAccounts
+        );
+        require_keys_eq!(
+            swap_vault.key(),
+            get_associated_token_address(&self.swap.key(), &accessory_mint.key()),
+            ShapelyError::InvalidSwapAccounts
+        );
+
+        let cpi_accounts = Create {
+            payer: self.maker.to_account_info(),
+            associated_token: maker_ata_info.clone(),
+            authority: self.maker.to_account_info(),
+            mint: accessory_mint_info.clone(),
+            system_program: self.system_program.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            cpi_accounts,
+        );
+
+        create_idempotent(cpi_ctx)?;
+
+        let cpi_accounts = TransferChecked {
+            from: swap_vault_info.clone(),
+            mint: accessory_mint_info.clone(),
+            to: maker_ata_info.clone(),
+            authority: self.swap.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        transfer_checked(cpi_ctx, swap_vault.amount, accessory_mint.decimals)?;
+
+        let cpi_accounts = CloseAccount {
+            account: swap_vault_info.clone(),
+            destination: self.maker.to_account_info(),
+            authority: self.swap.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        close_account(cpi_ctx)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ShapelyError, events::ProceedsClaimed, payment::transfer_lamports, state::Proceeds,
+};
+
+#[derive(Accounts)]
+pub struct ClaimProceeds<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"proceeds", seller.key().as_ref()],
+        bump = seller_proceeds.bump
+    )]
+    pub seller_proceeds: Account<'info, Proceeds>,
+}
+
+impl<'info> ClaimProceeds<'info> {
+    pub fn claim_proceeds(&mut self) -> Result<()> {
+        let amount = self.seller_proceeds.claim()?;
+
+        require!(amount > 0, ShapelyError::NothingToClaim);
+
+        transfer_lamports(
+            &self.seller_proceeds.to_account_info(),
+            &self.seller.to_account_info(),
+            amount,
+        )?;
+
+        emit!(ProceedsClaimed {
+            seller: self.seller.key(),
+            amount,
+            total_claimed: self.seller_proceeds.total_claimed,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
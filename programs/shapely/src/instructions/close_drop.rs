@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ShapelyError,
+    state::{Config, MintDrop},
+};
+
+#[derive(Accounts)]
+pub struct CloseDrop<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [drop.kind.seed(), config.key().as_ref()],
+        bump = drop.bump
+    )]
+    pub drop: Account<'info, MintDrop>,
+
+    #[account(
+        mut,
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+        has_one = admin @ ShapelyError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CloseDrop<'info> {
+    pub fn close_drop(&mut self) -> Result<()> {
+        self.config.set_drop_active(self.drop.kind, false);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{Metadata, MetadataAccount},
+    token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+use crate::{
+    error::ShapelyError,
+    state::{Auction, Config},
+};
+
+#[derive(Accounts)]
+pub struct CreateAuction<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub accessory_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = accessory_mint,
+        associated_token::authority = seller
+    )]
+    pub seller_accessory_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [
+            b"metadata".as_ref(),
+            metadata_program.key().as_ref(),
+            accessory_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub accessory_metadata: Account<'info, MetadataAccount>,
+
+    #[account(
+        init,
+        payer = seller,
+        seeds = [b"auction", accessory_mint.key().as_ref(), seller.key().as_ref()],
+        bump,
+        space = Auction::SPACE
+    )]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        init,
+        payer = seller,
+        associated_token::mint = accessory_mint,
+        associated_token::authority = auction
+    )]
+    pub auction_vault: Account<'info, TokenAccount>,
+
+    #[account(seeds = [b"config", config.seed.to_le_bytes().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub metadata_program: Program<'info, Metadata>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateAuction<'info> {
+    pub fn create_auction(
+        &mut self,
+        reserve_price: u64,
+        min_increment: u64,
+        end_time: i64,
+        bumps: &CreateAuctionBumps,
+    ) -> Result<()> {
+        self.config
+            .verify_accessory(&self.accessory_metadata, &self.accessory_mint.key())?;
+        require!(
+            end_time > Clock::get()?.unix_timestamp,
+            ShapelyError::InvalidAuctionEndTime
+        );
+        require!(min_increment > 0, ShapelyError::InvalidBidIncrement);
+
+        self.initialize_auction(reserve_price, min_increment, end_time, bumps.auction)?;
+        self.deposit_nft()?;
+        Ok(())
+    }
+
+    pub fn initialize_auction(
+        &mut self,
+        reserve_price: u64,
+        min_increment: u64,
+        end_time: i64,
+        bump: u8,
+    ) -> Result<()> {
+        self.auction.set_inner(Auction {
+            bump,
+            seller: self.seller.key(),
+            accessory_mint: self.accessory_mint.key(),
+            reserve_price,
+            min_increment,
+            end_time,
+            highest_bidder: None,
+            highest_bid: 0,
+        });
+
+        Ok(())
+    }
+
+    pub fn deposit_nft(&mut self) -> Result<()> {
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked {
+            from: self.seller_accessory_ata.to_account_info(),
+            mint: self.accessory_mint.to_account_info(),
+            to: self.auction_vault.to_account_info(),
+            authority: self.seller.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        transfer_checked(
+            cpi_ctx,
+            self.seller_accessory_ata.amount,
+            self.accessory_mint.decimals,
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ShapelyError,
+    state::{Config, DropClock, DropKind, MintDrop},
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DropParams {
+    pub clock: DropClock,
+    pub start: u64,
+    pub end: u64,
+    pub supply_cap: Option<u64>,
+    pub uri_prefix: String,
+    pub allowlist_root: Option<[u8; 32]>,
+}
+
+#[derive(Accounts)]
+#[instruction(kind: DropKind)]
+pub struct CreateDrop<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [kind.seed(), config.key().as_ref()],
+        bump,
+        space = MintDrop::SPACE
+    )]
+    pub drop: Account<'info, MintDrop>,
+
+    #[account(
+        mut,
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+        has_one = admin @ ShapelyError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateDrop<'info> {
+    pub fn create_drop(
+        &mut self,
+        kind: DropKind,
+        params: DropParams,
+        bumps: &CreateDropBumps,
+    ) -> Result<()> {
+        require!(params.start < params.end, ShapelyError::InvalidDropWindow);
+        require!(
+            params.uri_prefix.len() <= MintDrop::MAX_URI_PREFIX_LEN,
+            ShapelyError::InvalidDropUri
+        );
+
+        self.drop.set_inner(MintDrop {
+            bump: bumps.drop,
+            kind,
+            clock: params.clock,
+            start: params.start,
+            end: params.end,
+            supply_cap: params.supply_cap,
+            minted: 0,
+            uri_prefix: params.uri_prefix,
+            allowlist_root: params.allowlist_root,
+        });
+
+        self.config.set_drop_active(kind, true);
+
+        if kind == DropKind::Avatar {
+            self.config.avatar_drop_id = self
+                .config
+                .avatar_drop_id
+                .checked_add(1)
+                .ok_or(ShapelyError::MathOverflow)?;
+        }
+
+        Ok(())
+    }
+}
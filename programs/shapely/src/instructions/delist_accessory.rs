@@ -1,9 +1,21 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{
-    close_account, transfer_checked, CloseAccount, Mint, Token, TokenAccount, TransferChecked,
+use anchor_spl::{
+    metadata::{
+        mpl_token_metadata::instructions::{
+            ThawDelegatedAccountCpi, ThawDelegatedAccountCpiAccounts,
+        },
+        MasterEditionAccount, Metadata,
+    },
+    token::{
+        close_account, revoke, transfer_checked, CloseAccount, Mint, Revoke, Token, TokenAccount,
+        TransferChecked,
+    },
 };
 
-use crate::state::{Config, Listing};
+use crate::{
+    error::ShapelyError,
+    state::{Config, Listing},
+};
 
 #[derive(Accounts)]
 pub struct DelistAccessory<'info> {
@@ -27,18 +39,34 @@ pub struct DelistAccessory<'info> {
     )]
     pub listing: Account<'info, Listing>,
 
+    /// Only needed when the accessory is escrowed in a listing vault
     #[account(
         mut,
         associated_token::mint = accessory_mint,
         associated_token::authority = listing
     )]
-    pub listing_vault: Account<'info, TokenAccount>,
+    pub listing_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Only needed when the listing is delegated
+    #[account(
+        seeds = [
+            b"metadata".as_ref(),
+            metadata_program.key().as_ref(),
+            accessory_mint.key().as_ref(),
+            b"edition".as_ref(),
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub accessory_master_edition: Option<Account<'info, MasterEditionAccount>>,
 
     #[account(seeds = [b"config", config.seed.to_le_bytes().as_ref()], bump = config.bump)]
     pub config: Account<'info, Config>,
 
     pub token_program: Program<'info, Token>,
 
+    pub metadata_program: Program<'info, Metadata>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -52,16 +80,25 @@ impl<'info> DelistAccessory<'info> {
         ];
         let signer_seeds = &[&seeds[..]];
 
-        self.withdraw_nft(signer_seeds)?;
-        self.close_vault(signer_seeds)?;
+        if self.listing.delegated {
+            self.thaw_nft(signer_seeds)?;
+            self.revoke_listing()?;
+        } else {
+            self.withdraw_nft(signer_seeds)?;
+            self.close_vault(signer_seeds)?;
+        }
         Ok(())
     }
 
     pub fn withdraw_nft(&mut self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let Some(listing_vault) = self.listing_vault.as_ref() else {
+            return err!(ShapelyError::InvalidListingAccounts);
+        };
+
         let cpi_program = self.token_program.to_account_info();
 
         let cpi_accounts = TransferChecked {
-            from: self.listing_vault.to_account_info(),
+            from: listing_vault.to_account_info(),
             mint: self.accessory_mint.to_account_info(),
             to: self.artist_accessory_ata.to_account_info(),
             authority: self.listing.to_account_info(),
@@ -69,20 +106,20 @@ impl<'info> DelistAccessory<'info> {
 
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
 
-        transfer_checked(
-            cpi_ctx,
-            self.listing_vault.amount,
-            self.accessory_mint.decimals,
-        )?;
+        transfer_checked(cpi_ctx, listing_vault.amount, self.accessory_mint.decimals)?;
 
         Ok(())
     }
 
     pub fn close_vault(&mut self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let Some(listing_vault) = self.listing_vault.as_ref() else {
+            return err!(ShapelyError::InvalidListingAccounts);
+        };
+
         let cpi_program = self.token_program.to_account_info();
 
         let cpi_accounts = CloseAccount {
-            account: self.listing_vault.to_account_info(),
+            account: listing_vault.to_account_info(),
             destination: self.artist.to_account_info(),
             authority: self.listing.to_account_info(),
         };
@@ -93,4 +130,46 @@ impl<'info> DelistAccessory<'info> {
 
         Ok(())
     }
+
+    pub fn thaw_nft(&mut self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let Some(accessory_master_edition) = self.accessory_master_edition.as_ref() else {
+            return err!(ShapelyError::InvalidListingAccounts);
+        };
+
+        let metadata_program = &self.metadata_program.to_account_info();
+        let listing = &self.listing.to_account_info();
+        let artist_accessory_ata = &self.artist_accessory_ata.to_account_info();
+        let accessory_master_edition = &accessory_master_edition.to_account_info();
+        let accessory_mint = &self.accessory_mint.to_account_info();
+        let token_program = &self.token_program.to_account_info();
+
+        ThawDelegatedAccountCpi::new(
+            metadata_program,
+            ThawDelegatedAccountCpiAccounts {
+                delegate: listing,
+                token_account: artist_accessory_ata,
+                edition: accessory_master_edition,
+                mint: accessory_mint,
+                token_program,
+            },
+        )
+        .invoke_signed(signer_seeds)?;
+
+        Ok(())
+    }
+
+    pub fn revoke_listing(&mut self) -> Result<()> {
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = Revoke {
+            source: self.artist_accessory_ata.to_account_info(),
+            authority: self.artist.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        revoke(cpi_ctx)?;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::{create_idempotent, get_associated_token_address, AssociatedToken, Create},
+    token::{
+        close_account, transfer_checked, CloseAccount, Mint, Token, TokenAccount, TransferChecked,
+    },
+};
+
+use crate::{error::ShapelyError, state::BundleListing};
+
+#[derive(Accounts)]
+pub struct DelistBundle<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [b"bundle", seller.key().as_ref(), bundle.id.to_le_bytes().as_ref()],
+        bump = bundle.bump
+    )]
+    pub bundle: Account<'info, BundleListing>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DelistBundle<'info> {
+    /// Remaining accounts hold one group per accessory, in the bundle order:
+    /// `[accessory_mint, bundle_vault (mut), seller_accessory_ata (mut)]`
+    pub fn delist_bundle(&mut self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        require!(
+            remaining_accounts.len() == self.bundle.accessory_mints.len() * 3,
+            ShapelyError::InvalidBundleAccounts
+        );
+
+        let seller = self.seller.key();
+        let id_bytes = self.bundle.id.to_le_bytes();
+        let seeds = &[
+            b"bundle",
+            seller.as_ref(),
+            id_bytes.as_ref(),
+            &[self.bundle.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        for (index, accounts) in remaining_accounts.chunks(3).enumerate() {
+            self.withdraw_accessory(index, accounts, signer_seeds)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn withdraw_accessory(
+        &mut self,
+        index: usize,
+        accounts: &'info [AccountInfo<'info>],
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let [accessory_mint_info, bundle_vault_info, seller_ata_info] = accounts else {
+            return err!(ShapelyError::InvalidBundleAccounts);
+        };
+
+        let accessory_mint = Account::<Mint>::try_from(accessory_mint_info)?;
+        let bundle_vault = Account::<TokenAccount>::try_from(bundle_vault_info)?;
+
+        require_keys_eq!(
+            accessory_mint.key(),
+            self.bundle.accessory_mints[index],
+            ShapelyError::InvalidBundleAccounts
+        );
+        require_keys_eq!(
+            bundle_vault.key(),
+            get_associated_token_address(&self.bundle.key(), &accessory_mint.key()),
+            ShapelyError::InvalidBundleAccounts
+        );
+
+        let cpi_accounts = Create {
+            payer: self.seller.to_account_info(),
+            associated_token: seller_ata_info.clone(),
+            authority: self.seller.to_account_info(),
+            mint: accessory_mint_info.clone(),
+            system_program: self.system_program.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            cpi_accounts,
+        );
+
+        create_idempotent(cpi_ctx)?;
+
+        let cpi_accounts = TransferChecked {
+            from: bundle_vault_info.clone(),
+            mint: accessory_mint_info.clone(),
+            to: seller_ata_info.clone(),
+            authority: self.bundle.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        transfer_checked(cpi_ctx, bundle_vault.amount, accessory_mint.decimals)?;
+
+        let cpi_accounts = CloseAccount {
+            account: bundle_vault_info.clone(),
+            destination: self.seller.to_account_info(),
+            authority: self.bundle.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        close_account(cpi_ctx)?;
+
+        Ok(())
+    }
+}
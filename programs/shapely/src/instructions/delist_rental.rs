@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{
+    close_account, transfer_checked, CloseAccount, Mint, Token, TokenAccount, TransferChecked,
+};
+
+use crate::{error::ShapelyError, state::Rental};
+
+#[derive(Accounts)]
+pub struct DelistRental<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub accessory_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = accessory_mint,
+        associated_token::authority = owner
+    )]
+    pub owner_accessory_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"rental", accessory_mint.key().as_ref(), owner.key().as_ref()],
+        bump = rental.bump,
+        constraint = rental.renter.is_none() @ ShapelyError::AccessoryRented
+    )]
+    pub rental: Account<'info, Rental>,
+
+    #[account(
+        mut,
+        associated_token::mint = accessory_mint,
+        associated_token::authority = rental
+    )]
+    pub rental_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DelistRental<'info> {
+    pub fn delist_rental(&mut self) -> Result<()> {
+        let seeds = &[
+            b"rental",
+            self.accessory_mint.to_account_info().key.as_ref(),
+            self.owner.to_account_info().key.as_ref(),
+            &[self.rental.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        self.withdraw_nft(signer_seeds)?;
+        self.close_vault(signer_seeds)?;
+        Ok(())
+    }
+
+    pub fn withdraw_nft(&mut self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked {
+            from: self.rental_vault.to_account_info(),
+            mint: self.accessory_mint.to_account_info(),
+            to: self.owner_accessory_ata.to_account_info(),
+            authority: self.rental.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        transfer_checked(
+            cpi_ctx,
+            self.rental_vault.amount,
+            self.accessory_mint.decimals,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn close_vault(&mut self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = CloseAccount {
+            account: self.rental_vault.to_account_info(),
+            destination: self.owner.to_account_info(),
+            authority: self.rental.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        close_account(cpi_ctx)?;
+
+        Ok(())
+    }
+}
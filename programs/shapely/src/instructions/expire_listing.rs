@@ -0,0 +1,204 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{
+        mpl_token_metadata::instructions::{
+            ThawDelegatedAccountCpi, ThawDelegatedAccountCpiAccounts,
+        },
+        MasterEditionAccount, Metadata,
+    },
+    token::{
+        close_account, transfer_checked, CloseAccount, Mint, Token, TokenAccount, TransferChecked,
+    },
+};
+
+use crate::{error::ShapelyError, state::Listing};
+
+#[derive(Accounts)]
+pub struct ExpireListing<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    #[account(mut)]
+    /// CHECK: This will be validated in the listing seeds
+    pub artist: AccountInfo<'info>,
+
+    pub accessory_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = accessory_mint,
+        associated_token::authority = artist
+    )]
+    pub artist_accessory_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        close = artist,
+        seeds = [b"listing", accessory_mint.key().as_ref(), artist.key().as_ref()],
+        bump = listing.bump,
+        constraint = listing.is_expired(Clock::get()?.unix_timestamp) @ ShapelyError::ListingNotExpired
+    )]
+    pub listing: Account<'info, Listing>,
+
+    /// Escrows the accessory, or for delegated listings briefly holds it to clear the delegate
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = accessory_mint,
+        associated_token::authority = listing
+    )]
+    pub listing_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Only needed when the listing is delegated
+    #[account(
+        seeds = [
+            b"metadata".as_ref(),
+            metadata_program.key().as_ref(),
+            accessory_mint.key().as_ref(),
+            b"edition".as_ref(),
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub accessory_master_edition: Option<Account<'info, MasterEditionAccount>>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub metadata_program: Program<'info, Metadata>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ExpireListing<'info> {
+    pub fn expire_listing(&mut self) -> Result<()> {
+        let seeds = &[
+            b"listing",
+            self.accessory_mint.to_account_info().key.as_ref(),
+            self.artist.to_account_info().key.as_ref(),
+            &[self.listing.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        if self.listing.delegated {
+            self.thaw_nft(signer_seeds)?;
+            self.clear_delegate(signer_seeds)?;
+            self.close_vault(self.cranker.to_account_info(), signer_seeds)?;
+        } else {
+            self.withdraw_nft(signer_seeds)?;
+            self.close_vault(self.artist.to_account_info(), signer_seeds)?;
+        }
+        Ok(())
+    }
+
+    /// Revoking needs the seller's signature, so the listing instead spends its whole delegated
+    /// amount by routing the accessory through the vault and back, which clears the delegate
+    pub fn clear_delegate(&mut self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let Some(listing_vault) = self.listing_vault.as_ref() else {
+            return err!(ShapelyError::InvalidListingAccounts);
+        };
+
+        let amount = self.artist_accessory_ata.delegated_amount;
+
+        for (from, to) in [
+            (
+                self.artist_accessory_ata.to_account_info(),
+                listing_vault.to_account_info(),
+            ),
+            (
+                listing_vault.to_account_info(),
+                self.artist_accessory_ata.to_account_info(),
+            ),
+        ] {
+            let cpi_program = self.token_program.to_account_info();
+
+            let cpi_accounts = TransferChecked {
+                from,
+                mint: self.accessory_mint.to_account_info(),
+                to,
+                authority: self.listing.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+            transfer_checked(cpi_ctx, amount, self.accessory_mint.decimals)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn withdraw_nft(&mut self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let Some(listing_vault) = self.listing_vault.as_ref() else {
+            return err!(ShapelyError::InvalidListingAccounts);
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked {
+            from: listing_vault.to_account_info(),
+            mint: self.accessory_mint.to_account_info(),
+            to: self.artist_accessory_ata.to_account_info(),
+            authority: self.listing.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        transfer_checked(cpi_ctx, listing_vault.amount, self.accessory_mint.decimals)?;
+
+        Ok(())
+    }
+
+    pub fn close_vault(
+        &mut self,
+        destination: AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let Some(listing_vault) = self.listing_vault.as_ref() else {
+            return err!(ShapelyError::InvalidListingAccounts);
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = CloseAccount {
+            account: listing_vault.to_account_info(),
+            destination,
+            authority: self.listing.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        close_account(cpi_ctx)?;
+
+        Ok(())
+    }
+
+    pub fn thaw_nft(&mut self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let Some(accessory_master_edition) = self.accessory_master_edition.as_ref() else {
+            return err!(ShapelyError::InvalidListingAccounts);
+        };
+
+        let metadata_program = &self.metadata_program.to_account_info();
+        let listing = &self.listing.to_account_info();
+        let artist_accessory_ata = &self.artist_accessory_ata.to_account_info();
+        let accessory_master_edition = &accessory_master_edition.to_account_info();
+        let accessory_mint = &self.accessory_mint.to_account_info();
+        let token_program = &self.token_program.to_account_info();
+
+        ThawDelegatedAccountCpi::new(
+            metadata_program,
+            ThawDelegatedAccountCpiAccounts {
+                delegate: listing,
+                token_account: artist_accessory_ata,
+                edition: accessory_master_edition,
+                mint: accessory_mint,
+                token_program,
+            },
+        )
+        .invoke_signed(signer_seeds)?;
+
+        Ok(())
+    }
+}
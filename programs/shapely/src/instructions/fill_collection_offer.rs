@@ -0,0 +1,219 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{Metadata, MetadataAccount},
+    token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+use crate::{
+    error::ShapelyError,
+    payment::{transfer_lamports, SaleSplit},
+    state::{AccessoryRecord, CollectionOffer, Config},
+};
+
+#[derive(Accounts)]
+pub struct FillCollectionOffer<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(mut)]
+    /// CHECK: This will be validated in the collection offer seeds
+    pub buyer: AccountInfo<'info>,
+
+    /// The avatar accounts are only needed when the config avatar policy requires it
+    #[account(
+        seeds = [b"avatar", buyer.key().as_ref(), config.avatar_collection.key().as_ref()],
+        bump,
+    )]
+    pub buyer_avatar_mint: Option<Box<Account<'info, Mint>>>,
+
+    /// Validated against the avatar mint and collection in the handler
+    pub buyer_avatar_metadata: Option<Box<Account<'info, MetadataAccount>>>,
+
+    pub accessory_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [
+            b"metadata".as_ref(),
+            metadata_program.key().as_ref(),
+            accessory_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub accessory_metadata: Box<Account<'info, MetadataAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"accessory", accessory_mint.key().as_ref()],
+        bump = accessory_record.bump
+    )]
+    pub accessory_record: Box<Account<'info, AccessoryRecord>>,
+
+    #[account(
+        mut,
+        associated_token::mint = accessory_mint,
+        associated_token::authority = seller
+    )]
+    pub seller_accessory_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = accessory_mint,
+        associated_token::authority = buyer
+    )]
+    pub buyer_accessory_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"collection offer",
+            config.key().as_ref(),
+            buyer.key().as_ref(),
+            collection_offer.id.to_le_bytes().as_ref()
+        ],
+        bump = collection_offer.bump
+    )]
+    pub collection_offer: Account<'info, CollectionOffer>,
+
+    #[account(seeds = [b"config", config.seed.to_le_bytes().as_ref()], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(mut, seeds = [b"treasury", config.key().as_ref()], bump = config.treasury_bump)]
+    pub treasury: SystemAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub metadata_program: Program<'info, Metadata>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FillCollectionOffer<'info> {
+    /// Remaining accounts hold one `[creator (mut)]` per creator in the accessory record
+    pub fn fill_collection_offer(
+        &mut self,
+        creator_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        self.config
+            .verify_accessory(&self.accessory_metadata, &self.accessory_mint.key())?;
+        require!(
+            self.collection_offer.filter.matches(&self.accessory_record),
+            ShapelyError::OfferFilterMismatch
+        );
+
+        self.verify_buyer_avatar()?;
+        self.process_payment(creator_accounts)?;
+        self.deliver_nft()?;
+        self.lock_metadata()?;
+        self.record_fill()?;
+        Ok(())
+    }
+
+    pub fn verify_buyer_avatar(&self) -> Result<()> {
+        let primary_sale = self.seller.key() == self.accessory_record.artist;
+        let avatar_mint = self.buyer_avatar_mint.as_ref().map(|mint| mint.key());
+        let avatar = self
+            .buyer_avatar_metadata
+            .as_deref()
+            .zip(avatar_mint.as_ref())
+            .map(|(metadata, mint)| (&**metadata, mint));
+
+        self.config.verify_buyer_avatar(avatar, primary_sale)
+    }
+
+    pub fn process_payment(&mut self, creator_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        require!(
+            creator_accounts.len() == self.accessory_record.creators.len(),
+            ShapelyError::InvalidCreator
+        );
+
+        let seller = self.seller.key();
+        let royalty_bps = self.accessory_record.royalty_bps_for(&seller);
+
+        let split = SaleSplit::new(self.collection_offer.price, self.config.fee, royalty_bps)?;
+
+        let (creator_amounts, seller_amount) =
+            self.accessory_record.creator_payouts(&seller, &split)?;
+
+        let collection_offer = self.collection_offer.to_account_info();
+
+        // 1. Collect fees
+        transfer_lamports(
+            &collection_offer,
+            &self.treasury.to_account_info(),
+            split.fee,
+        )?;
+
+        // 2. Pay creators their share of the sale or royalty
+        for ((creator, amount), creator_info) in self
+            .accessory_record
+            .creators
+            .iter()
+            .zip(creator_amounts)
+            .zip(creator_accounts)
+        {
+            require_keys_eq!(
+                creator_info.key(),
+                creator.address,
+                ShapelyError::InvalidCreator
+            );
+
+            if amount == 0 {
+                continue;
+            }
+
+            transfer_lamports(&collection_offer, creator_info, amount)?;
+        }
+
+        // 3. Payout seller
+        transfer_lamports(
+            &collection_offer,
+            &self.seller.to_account_info(),
+            seller_amount,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn deliver_nft(&mut self) -> Result<()> {
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked {
+            from: self.seller_accessory_ata.to_account_info(),
+            mint: self.accessory_mint.to_account_info(),
+            to: self.buyer_accessory_ata.to_account_info(),
+            authority: self.seller.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        transfer_checked(cpi_ctx, 1, self.accessory_mint.decimals)?;
+
+        Ok(())
+    }
+
+    pub fn lock_metadata(&mut self) -> Result<()> {
+        self.accessory_record.metadata_locked = true;
+
+        Ok(())
+    }
+
+    /// Closes the offer and refunds the rent to the buyer once every unit is bought
+    pub fn record_fill(&mut self) -> Result<()> {
+        self.collection_offer.remaining = self
+            .collection_offer
+            .remaining
+            .checked_sub(1)
+            .ok_or(ShapelyError::MathOverflow)?;
+
+        if self.collection_offer.remaining == 0 {
+            self.collection_offer.close(self.buyer.to_account_info())?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,226 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::{
+    associated_token::{create_idempotent, get_associated_token_address, AssociatedToken, Create},
+    metadata::MetadataAccount,
+    token::{
+        close_account, transfer_checked, CloseAccount, Mint, Token, TokenAccount, TransferChecked,
+    },
+};
+
+use crate::{
+    error::ShapelyError,
+    state::{Config, SwapOffer},
+};
+
+#[derive(Accounts)]
+pub struct FillSwap<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+
+    #[account(mut)]
+    /// CHECK: This is validated in the swap seeds
+    pub maker: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = maker,
+        seeds = [b"swap", maker.key().as_ref(), swap.id.to_le_bytes().as_ref()],
+        bump = swap.bump,
+        has_one = config @ ShapelyError::SwapConfigMismatch
+    )]
+    pub swap: Account<'info, SwapOffer>,
+
+    #[account(seeds = [b"config", config.seed.to_le_bytes().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"treasury", config.key().as_ref()], bump = config.treasury_bump)]
+    pub treasury: SystemAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FillSwap<'info> {
+    /// Remaining accounts hold one group per requested accessory, in the swap order:
+    /// `[accessory_mint, accessory_metadata, taker_accessory_ata (mut), maker_accessory_ata (mut)]`
+    /// followed by one group per offered accessory, in the swap order:
+    /// `[accessory_mint, swap_vault (mut), taker_accessory_ata (mut)]`
+    pub fn fill_swap(&mut self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        let requested_len = self.swap.requested_mints.len() * 4;
+
+        require!(
+            remaining_accounts.len() == requested_len + self.swap.offered_mints.len() * 3,
+            ShapelyError::InvalidSwapAccounts
+        );
+
+        let (requested_accounts, offered_accounts) = remaining_accounts.split_at(requested_len);
+
+        for (index, accounts) in requested_accounts.chunks(4).enumerate() {
+            self.send_requested_accessory(index, accounts)?;
+        }
+
+        let maker = self.maker.key();
+        let id_bytes = self.swap.id.to_le_bytes();
+        let seeds = &[
+            b"swap",
+            maker.as_ref(),
+            id_bytes.as_ref(),
+            &[self.swap.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        for (index, accounts) in offered_accounts.chunks(3).enumerate() {
+            self.receive_offered_accessory(index, accounts, signer_seeds)?;
+        }
+
+        self.pay_sol(self.maker.to_account_info(), self.swap.sol_top_up)?;
+        self.pay_sol(self.treasury.to_account_info(), self.config.swap_fee)
+    }
+
+    pub fn send_requested_accessory(
+        &mut self,
+        index: usize,
+        accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        let [accessory_mint_info, accessory_metadata_info, taker_ata_info, maker_ata_info] =
+            accounts
+        else {
+            return err!(ShapelyError::InvalidSwapAccounts);
+        };
+
+        let accessory_mint = Account::<Mint>::try_from(accessory_mint_info)?;
+        let accessory_metadata = Account::<MetadataAccount>::try_from(accessory_metadata_info)?;
+        let taker_ata = Account::<TokenAccount>::try_from(taker_ata_info)?;
+
+        require_keys_eq!(
+            accessory_mint.key(),
+            self.swap.requested_mints[index],
+            ShapelyError::InvalidSwapAccounts
+        );
+        self.config
+            .verify_accessory(&accessory_metadata, &accessory_mint.key())?;
+        require_keys_eq!(
+            taker_ata.owner,
+            self.taker.key(),
+            ShapelyError::InvalidSwapAccounts
+        );
+
+        let cpi_accounts = Create {
+            payer: self.taker.to_account_info(),
+            associated_token: maker_ata_info.clone(),
+            authority: self.maker.to_account_info(),
+            mint: accessory_mint_info.clone(),
+            system_program: self.system_program.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            cpi_accounts,
+        );
+
+        create_idempotent(cpi_ctx)?;
+
+        let cpi_accounts = TransferChecked {
+            from: taker_ata_info.clone(),
+            mint: accessory_mint_info.clone(),
+            to: maker_ata_info.clone(),
+            authority: self.taker.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+
+        transfer_checked(cpi_ctx, 1, accessory_mint.decimals)
+    }
+
+    pub fn receive_offered_accessory(
+        &mut self,
+        index: usize,
+        accounts: &'info [AccountInfo<'info>],
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let [accessory_mint_info, swap_vault_info, taker_ata_info] = accounts else {
+            return err!(ShapelyError::InvalidSwapAccounts);
+        };
+
+        let accessory_mint = Account::<Mint>::try_from(accessory_mint_info)?;
+        let swap_vault = Account::<TokenAccount>::try_from(swap_vault_info)?;
+
+        require_keys_eq!(
+            accessory_mint.key(),
+            self.swap.offered_mints[index],
+            ShapelyError::InvalidSwapAccounts
+        );
+        require_keys_eq!(
+            swap_vault.key(),
+            get_associated_token_address(&self.swap.key(), &accessory_mint.key()),
+            ShapelyError::InvalidSwapAccounts
+        );
+
+        let cpi_accounts = Create {
+            payer: self.taker.to_account_info(),
+            associated_token: taker_ata_info.clone(),
+            authority: self.taker.to_account_info(),
+            mint: accessory_mint_info.clone(),
+            system_program: self.system_program.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            cpi_accounts,
+        );
+
+        create_idempotent(cpi_ctx)?;
+
+        let cpi_accounts = TransferChecked {
+            from: swap_vault_info.clone(),
+            mint: accessory_mint_info.clone(),
+            to: taker_ata_info.clone(),
+            authority: self.swap.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        transfer_checked(cpi_ctx, swap_vault.amount, accessory_mint.decimals)?;
+
+        let cpi_accounts = CloseAccount {
+            account: swap_vault_info.clone(),
+            destination: self.maker.to_account_info(),
+            authority: self.swap.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        close_account(cpi_ctx)
+    }
+
+    pub fn pay_sol(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let cpi_accounts = Transfer {
+            from: self.taker.to_account_info(),
+            to,
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+        transfer(cpi_ctx, amount)
+    }
+}
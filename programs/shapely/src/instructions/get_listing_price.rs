@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::state::Listing;
+
+#[derive(Accounts)]
+pub struct GetListingPrice<'info> {
+    /// CHECK: This will be validated in the listing seeds
+    pub artist: AccountInfo<'info>,
+
+    pub accessory_mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"listing", accessory_mint.key().as_ref(), artist.key().as_ref()],
+        bump = listing.bump
+    )]
+    pub listing: Account<'info, Listing>,
+}
+
+impl<'info> GetListingPrice<'info> {
+    pub fn get_listing_price(&self) -> Result<u64> {
+        self.listing.price_at(Clock::get()?.unix_timestamp)
+    }
+}
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{Metadata, MetadataAccount},
+    token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+use crate::{
+    constants::MAX_GIFT_MESSAGE_LEN, error::ShapelyError, events::AccessoryGifted, state::Config,
+};
+
+#[derive(Accounts)]
+pub struct GiftAccessory<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    /// CHECK: This is validated as the holder of the avatar
+    pub recipient: AccountInfo<'info>,
+
+    pub avatar_mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [
+            b"metadata".as_ref(),
+            metadata_program.key().as_ref(),
+            avatar_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub avatar_metadata: Account<'info, MetadataAccount>,
+
+    #[account(
+        token::mint = avatar_mint,
+        token::authority = recipient,
+        constraint = recipient_avatar_ata.amount == 1 @ ShapelyError::AvatarNotHeld
+    )]
+    pub recipient_avatar_ata: Account<'info, TokenAccount>,
+
+    pub accessory_mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [
+            b"metadata".as_ref(),
+            metadata_program.key().as_ref(),
+            accessory_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub accessory_metadata: Account<'info, MetadataAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = accessory_mint,
+        associated_token::authority = sender,
+        constraint = sender_accessory_ata.amount == 1 @ ShapelyError::AccessoryNotHeld
+    )]
+    pub sender_accessory_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = sender,
+        associated_token::mint = accessory_mint,
+        associated_token::authority = recipient
+    )]
+    pub recipient_accessory_ata: Account<'info, TokenAccount>,
+
+    #[account(seeds = [b"config", config.seed.to_le_bytes().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub metadata_program: Program<'info, Metadata>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> GiftAccessory<'info> {
+    pub fn gift_accessory(&mut self, message: Option<String>) -> Result<()> {
+        if let Some(message) = message.as_ref() {
+            require!(
+                message.len() <= MAX_GIFT_MESSAGE_LEN,
+                ShapelyError::GiftMessageTooLong
+            );
+        }
+
+        self.config
+            .verify_avatar(&self.avatar_metadata, &self.avatar_mint.key())?;
+        self.config
+            .verify_accessory(&self.accessory_metadata, &self.accessory_mint.key())?;
+
+        self.send_nft()?;
+
+        emit!(AccessoryGifted {
+            accessory_mint: self.accessory_mint.key(),
+            sender: self.sender.key(),
+            recipient: self.recipient.key(),
+            avatar_mint: self.avatar_mint.key(),
+            message,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn send_nft(&mut self) -> Result<()> {
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked {
+            from: self.sender_accessory_ata.to_account_info(),
+            mint: self.accessory_mint.to_account_info(),
+            to: self.recipient_accessory_ata.to_account_info(),
+            authority: self.sender.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        transfer_checked(cpi_ctx, 1, self.accessory_mint.decimals)?;
+
+        Ok(())
+    }
+}
@@ -16,7 +16,7 @@ use anchor_spl::{
     token::{mint_to, Mint, MintTo, Token, TokenAccount},
 };
 
-use crate::state::Config;
+use crate::state::{AvatarPolicy, Config};
 
 #[derive(Accounts)]
 #[instruction(seed: u64)]
@@ -173,8 +173,20 @@ impl<'info> Initialize<'info> {
             accessory_collection_bump: bumps.accessory_collection,
             avatar_collection: self.avatar_collection.key(),
             accessory_collection: self.accessory_collection.key(),
-            seed,
             fee,
+            seed,
+            admin: self.payer.key(),
+            metadata_updates_enabled: true,
+            avatar_drop_active: false,
+            accessory_drop_active: false,
+            accessory_mint_cap: 0,
+            mint_cap_period: 0,
+            swap_fee: 0,
+            referral_bps: 0,
+            avatar_policy: AvatarPolicy::Required,
+            payment_mints: Vec::new(),
+            avatar_drop_id: 0,
+            referrers: Vec::new(),
         });
 
         Ok(())
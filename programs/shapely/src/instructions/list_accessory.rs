@@ -7,7 +7,8 @@ use anchor_spl::{
 
 use crate::{
     error::ShapelyError,
-    state::{Config, Listing},
+    events::AccessoryListed,
+    state::{Config, DutchAuction, Listing, Proceeds},
 };
 
 #[derive(Accounts)]
@@ -40,11 +41,7 @@ pub struct ListAccessory<'info> {
             accessory_mint.key().as_ref(),
         ],
         bump,
-        seeds::program = metadata_program.key(),
-
-        constraint = accessory_metadata.collection.as_ref().unwrap().verified == true @ ShapelyError::AccessoryNotVerified,
-        constraint = accessory_metadata.collection.as_ref().unwrap().key.as_ref() ==
-        accessory_collection.key().as_ref() @ ShapelyError::AccessoryNotVerified
+        seeds::program = metadata_program.key()
     )]
     pub accessory_metadata: Account<'info, MetadataAccount>,
 
@@ -77,6 +74,16 @@ pub struct ListAccessory<'info> {
     )]
     pub listing_vault: Account<'info, TokenAccount>,
 
+    /// Only needed when the seller wants the sale credited to their proceeds account
+    #[account(
+        init_if_needed,
+        payer = artist,
+        seeds = [b"proceeds", artist.key().as_ref()],
+        bump,
+        space = Proceeds::SPACE
+    )]
+    pub seller_proceeds: Option<Account<'info, Proceeds>>,
+
     #[account(seeds = [b"config", config.seed.to_le_bytes().as_ref()], bump = config.bump)]
     pub config: Account<'info, Config>,
 
@@ -90,17 +97,98 @@ pub struct ListAccessory<'info> {
 }
 
 impl<'info> ListAccessory<'info> {
-    pub fn list_accessory(&mut self, price: u64, bumps: &ListAccessoryBumps) -> Result<()> {
-        self.initialize_listing(price, bumps.listing)?;
+    pub fn list_accessory(
+        &mut self,
+        price: u64,
+        payment_mint: Option<Pubkey>,
+        expires_at: Option<i64>,
+        reserved_buyer: Option<Pubkey>,
+        bumps: &ListAccessoryBumps,
+    ) -> Result<()> {
+        self.config
+            .verify_accessory(&self.accessory_metadata, &self.accessory_mint.key())?;
+        self.config
+            .validate_listing_terms(payment_mint, expires_at)?;
+        if let Some(seller_proceeds) = self.seller_proceeds.as_mut() {
+            seller_proceeds.open(
+                self.artist.key(),
+                payment_mint,
+                bumps.seller_proceeds.unwrap_or_default(),
+            )?;
+        }
+        self.initialize_listing(
+            price,
+            payment_mint,
+            expires_at,
+            None,
+            reserved_buyer,
+            bumps.listing,
+        )?;
         self.deposit_nft()?;
         Ok(())
     }
 
-    pub fn initialize_listing(&mut self, price: u64, bump: u8) -> Result<()> {
+    pub fn list_accessory_dutch(
+        &mut self,
+        dutch_auction: DutchAuction,
+        payment_mint: Option<Pubkey>,
+        expires_at: Option<i64>,
+        reserved_buyer: Option<Pubkey>,
+        bumps: &ListAccessoryBumps,
+    ) -> Result<()> {
+        dutch_auction.validate()?;
+        self.config
+            .verify_accessory(&self.accessory_metadata, &self.accessory_mint.key())?;
+        self.config
+            .validate_listing_terms(payment_mint, expires_at)?;
+        if let Some(seller_proceeds) = self.seller_proceeds.as_mut() {
+            seller_proceeds.open(
+                self.artist.key(),
+                payment_mint,
+                bumps.seller_proceeds.unwrap_or_default(),
+            )?;
+        }
+        self.initialize_listing(
+            dutch_auction.start_price,
+            payment_mint,
+            expires_at,
+            Some(dutch_auction),
+            reserved_buyer,
+            bumps.listing,
+        )?;
+        self.deposit_nft()?;
+        Ok(())
+    }
+
+    pub fn initialize_listing(
+        &mut self,
+        price: u64,
+        payment_mint: Option<Pubkey>,
+        expires_at: Option<i64>,
+        dutch_auction: Option<DutchAuction>,
+        reserved_buyer: Option<Pubkey>,
+        bump: u8,
+    ) -> Result<()> {
         self.listing.set_inner(Listing {
             bump,
             price,
             accessory_mint: self.accessory_mint.key(),
+            payment_mint,
+            expires_at,
+            dutch_auction,
+            delegated: false,
+            claimable_proceeds: self.seller_proceeds.is_some(),
+            reserved_buyer,
+        });
+
+        emit!(AccessoryListed {
+            listing: self.listing.key(),
+            accessory_mint: self.accessory_mint.key(),
+            seller: self.artist.key(),
+            price: self.listing.price,
+            payment_mint: self.listing.payment_mint,
+            reserved_buyer: self.listing.reserved_buyer,
+            timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
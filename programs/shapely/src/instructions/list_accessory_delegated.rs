@@ -0,0 +1,188 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    metadata::{
+        mpl_token_metadata::instructions::{
+            FreezeDelegatedAccountCpi, FreezeDelegatedAccountCpiAccounts,
+        },
+        MasterEditionAccount, Metadata, MetadataAccount,
+    },
+    token::{approve, Approve, Mint, Token, TokenAccount},
+};
+
+use crate::{
+    error::ShapelyError,
+    events::AccessoryListed,
+    state::{Config, Listing, Proceeds},
+};
+
+#[derive(Accounts)]
+pub struct ListAccessoryDelegated<'info> {
+    #[account(mut)]
+    pub artist: Signer<'info>,
+
+    pub accessory_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = accessory_mint,
+        associated_token::authority = artist,
+        constraint = artist_accessory_ata.amount == 1 @ ShapelyError::InvalidListingAccounts
+    )]
+    pub artist_accessory_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = ["accessory collection".as_bytes(), config.key().as_ref()],
+        bump = config.accessory_collection_bump,
+        constraint = accessory_collection.key() == config.accessory_collection.key() @ ShapelyError::InvalidCollectionMint
+    )]
+    pub accessory_collection: Account<'info, Mint>,
+
+    #[account(
+        seeds = [
+            b"metadata".as_ref(),
+            metadata_program.key().as_ref(),
+            accessory_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub accessory_metadata: Account<'info, MetadataAccount>,
+
+    #[account(
+        seeds = [
+            b"metadata".as_ref(),
+            metadata_program.key().as_ref(),
+            accessory_mint.key().as_ref(),
+            b"edition".as_ref(),
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub accessory_master_edition: Account<'info, MasterEditionAccount>,
+
+    #[account(
+        init,
+        payer = artist,
+        seeds = [b"listing", accessory_mint.key().as_ref(), artist.key().as_ref()],
+        bump,
+        space = Listing::SPACE
+    )]
+    pub listing: Account<'info, Listing>,
+
+    /// Only needed when the seller wants the sale credited to their proceeds account
+    #[account(
+        init_if_needed,
+        payer = artist,
+        seeds = [b"proceeds", artist.key().as_ref()],
+        bump,
+        space = Proceeds::SPACE
+    )]
+    pub seller_proceeds: Option<Account<'info, Proceeds>>,
+
+    #[account(seeds = [b"config", config.seed.to_le_bytes().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub metadata_program: Program<'info, Metadata>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ListAccessoryDelegated<'info> {
+    pub fn list_accessory_delegated(
+        &mut self,
+        price: u64,
+        payment_mint: Option<Pubkey>,
+        expires_at: Option<i64>,
+        reserved_buyer: Option<Pubkey>,
+        bumps: &ListAccessoryDelegatedBumps,
+    ) -> Result<()> {
+        self.config
+            .verify_accessory(&self.accessory_metadata, &self.accessory_mint.key())?;
+        self.config
+            .validate_listing_terms(payment_mint, expires_at)?;
+        if let Some(seller_proceeds) = self.seller_proceeds.as_mut() {
+            seller_proceeds.open(
+                self.artist.key(),
+                payment_mint,
+                bumps.seller_proceeds.unwrap_or_default(),
+            )?;
+        }
+
+        self.listing.set_inner(Listing {
+            bump: bumps.listing,
+            price,
+            accessory_mint: self.accessory_mint.key(),
+            payment_mint,
+            expires_at,
+            dutch_auction: None,
+            delegated: true,
+            claimable_proceeds: self.seller_proceeds.is_some(),
+            reserved_buyer,
+        });
+
+        emit!(AccessoryListed {
+            listing: self.listing.key(),
+            accessory_mint: self.accessory_mint.key(),
+            seller: self.artist.key(),
+            price: self.listing.price,
+            payment_mint: self.listing.payment_mint,
+            reserved_buyer: self.listing.reserved_buyer,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        let seeds = &[
+            b"listing",
+            self.accessory_mint.to_account_info().key.as_ref(),
+            self.artist.to_account_info().key.as_ref(),
+            &[bumps.listing],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        self.approve_listing()?;
+        self.freeze_nft(signer_seeds)?;
+        Ok(())
+    }
+
+    pub fn approve_listing(&mut self) -> Result<()> {
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = Approve {
+            to: self.artist_accessory_ata.to_account_info(),
+            delegate: self.listing.to_account_info(),
+            authority: self.artist.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        approve(cpi_ctx, self.artist_accessory_ata.amount)?;
+
+        Ok(())
+    }
+
+    /// The master edition holds the mint's freeze authority, so the freeze goes through
+    /// token metadata with the listing signing as delegate
+    pub fn freeze_nft(&mut self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let metadata_program = &self.metadata_program.to_account_info();
+        let listing = &self.listing.to_account_info();
+        let artist_accessory_ata = &self.artist_accessory_ata.to_account_info();
+        let accessory_master_edition = &self.accessory_master_edition.to_account_info();
+        let accessory_mint = &self.accessory_mint.to_account_info();
+        let token_program = &self.token_program.to_account_info();
+
+        FreezeDelegatedAccountCpi::new(
+            metadata_program,
+            FreezeDelegatedAccountCpiAccounts {
+                delegate: listing,
+                token_account: artist_accessory_ata,
+                edition: accessory_master_edition,
+                mint: accessory_mint,
+                token_program,
+            },
+        )
+        .invoke_signed(signer_seeds)?;
+
+        Ok(())
+    }
+}
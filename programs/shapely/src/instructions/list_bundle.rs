@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::{create, get_associated_token_address, AssociatedToken, Create},
+    token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+use crate::{
+    error::ShapelyError,
+    state::{AccessoryRecord, BundleListing, Config},
+};
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct ListBundle<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(
+        init,
+        payer = seller,
+        seeds = [b"bundle", seller.key().as_ref(), id.to_le_bytes().as_ref()],
+        bump,
+        space = BundleListing::SPACE
+    )]
+    pub bundle: Account<'info, BundleListing>,
+
+    #[account(seeds = [b"config", config.seed.to_le_bytes().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ListBundle<'info> {
+    /// Remaining accounts hold one group per accessory:
+    /// `[accessory_mint, accessory_record, seller_accessory_ata (mut), bundle_vault (mut)]`
+    pub fn list_bundle(
+        &mut self,
+        id: u64,
+        price: u64,
+        remaining_accounts: &'info [AccountInfo<'info>],
+        bumps: &ListBundleBumps,
+    ) -> Result<()> {
+        let accessory_count = remaining_accounts.len() / 4;
+
+        require!(
+            accessory_count > 0
+                && accessory_count * 4 == remaining_accounts.len()
+                && accessory_count <= BundleListing::MAX_ACCESSORIES,
+            ShapelyError::InvalidBundleAccounts
+        );
+
+        let mut accessory_mints = Vec::with_capacity(accessory_count);
+
+        for accounts in remaining_accounts.chunks(4) {
+            let accessory_mint = self.deposit_accessory(accounts)?;
+
+            require!(
+                !accessory_mints.contains(&accessory_mint),
+                ShapelyError::InvalidBundleAccounts
+            );
+            accessory_mints.push(accessory_mint);
+        }
+
+        self.bundle.set_inner(BundleListing {
+            bump: bumps.bundle,
+            seller: self.seller.key(),
+            id,
+            price,
+            accessory_mints,
+        });
+
+        Ok(())
+    }
+
+    pub fn deposit_accessory(&mut self, accounts: &'info [AccountInfo<'info>]) -> Result<Pubkey> {
+        let [accessory_mint_info, accessory_record_info, seller_ata_info, bundle_vault_info] =
+            accounts
+        else {
+            return err!(ShapelyError::InvalidBundleAccounts);
+        };
+
+        let accessory_mint = Account::<Mint>::try_from(accessory_mint_info)?;
+        let accessory_record = Account::<AccessoryRecord>::try_from(accessory_record_info)?;
+        let seller_ata = Account::<TokenAccount>::try_from(seller_ata_info)?;
+
+        // Records are only ever created at the PDA of the mint they store
+        require_keys_eq!(
+            accessory_record.accessory_mint,
+            accessory_mint.key(),
+            ShapelyError::InvalidBundleAccounts
+        );
+        // Bundles are sets sold by their own artist, so no royalty is ever owed on them
+        require_keys_eq!(
+            accessory_record.artist,
+            self.seller.key(),
+            ShapelyError::NotAccessoryArtist
+        );
+
+        require_keys_eq!(
+            seller_ata.key(),
+            get_associated_token_address(&self.seller.key(), &accessory_mint.key()),
+            ShapelyError::InvalidBundleAccounts
+        );
+        require!(seller_ata.amount == 1, ShapelyError::InvalidBundleAccounts);
+        require_keys_eq!(
+            bundle_vault_info.key(),
+            get_associated_token_address(&self.bundle.key(), &accessory_mint.key()),
+            ShapelyError::InvalidBundleAccounts
+        );
+
+        let cpi_accounts = Create {
+            payer: self.seller.to_account_info(),
+            associated_token: bundle_vault_info.clone(),
+            authority: self.bundle.to_account_info(),
+            mint: accessory_mint_info.clone(),
+            system_program: self.system_program.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            cpi_accounts,
+        );
+
+        create(cpi_ctx)?;
+
+        let cpi_accounts = TransferChecked {
+            from: seller_ata_info.clone(),
+            mint: accessory_mint_info.clone(),
+            to: bundle_vault_info.clone(),
+            authority: self.seller.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+
+        transfer_checked(cpi_ctx, seller_ata.amount, accessory_mint.decimals)?;
+
+        Ok(accessory_mint.key())
+    }
+}
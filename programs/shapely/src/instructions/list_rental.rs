@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{Metadata, MetadataAccount},
+    token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+use crate::{
+    error::ShapelyError,
+    state::{Config, Rental},
+};
+
+#[derive(Accounts)]
+pub struct ListRental<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub accessory_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = accessory_mint,
+        associated_token::authority = owner
+    )]
+    pub owner_accessory_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [
+            b"metadata".as_ref(),
+            metadata_program.key().as_ref(),
+            accessory_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub accessory_metadata: Account<'info, MetadataAccount>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"rental", accessory_mint.key().as_ref(), owner.key().as_ref()],
+        bump,
+        space = Rental::SPACE
+    )]
+    pub rental: Account<'info, Rental>,
+
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = accessory_mint,
+        associated_token::authority = rental
+    )]
+    pub rental_vault: Account<'info, TokenAccount>,
+
+    #[account(seeds = [b"config", config.seed.to_le_bytes().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub metadata_program: Program<'info, Metadata>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ListRental<'info> {
+    pub fn list_rental(
+        &mut self,
+        price_per_period: u64,
+        period: i64,
+        max_periods: u32,
+        bumps: &ListRentalBumps,
+    ) -> Result<()> {
+        self.config
+            .verify_accessory(&self.accessory_metadata, &self.accessory_mint.key())?;
+        require!(
+            price_per_period > 0 && period > 0 && max_periods > 0,
+            ShapelyError::InvalidRentalTerms
+        );
+
+        self.rental.set_inner(Rental {
+            bump: bumps.rental,
+            owner: self.owner.key(),
+            accessory_mint: self.accessory_mint.key(),
+            price_per_period,
+            period,
+            max_periods,
+            renter: None,
+            rented_until: 0,
+        });
+
+        self.deposit_nft()?;
+        Ok(())
+    }
+
+    pub fn deposit_nft(&mut self) -> Result<()> {
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked {
+            from: self.owner_accessory_ata.to_account_info(),
+            mint: self.accessory_mint.to_account_info(),
+            to: self.rental_vault.to_account_info(),
+            authority: self.owner.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        transfer_checked(
+            cpi_ctx,
+            self.owner_accessory_ata.amount,
+            self.accessory_mint.decimals,
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{
+    error::ShapelyError,
+    state::{CollectionOffer, Config, OfferFilter},
+};
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct MakeCollectionOffer<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = buyer,
+        seeds = [
+            b"collection offer",
+            config.key().as_ref(),
+            buyer.key().as_ref(),
+            id.to_le_bytes().as_ref()
+        ],
+        bump,
+        space = CollectionOffer::SPACE
+    )]
+    pub collection_offer: Account<'info, CollectionOffer>,
+
+    #[account(seeds = [b"config", config.seed.to_le_bytes().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MakeCollectionOffer<'info> {
+    pub fn make_collection_offer(
+        &mut self,
+        id: u64,
+        filter: OfferFilter,
+        price: u64,
+        quantity: u32,
+        bumps: &MakeCollectionOfferBumps,
+    ) -> Result<()> {
+        require!(price > 0 && quantity > 0, ShapelyError::InvalidOfferAmount);
+
+        let amount = price
+            .checked_mul(quantity as u64)
+            .ok_or(ShapelyError::MathOverflow)?;
+
+        self.collection_offer.set_inner(CollectionOffer {
+            bump: bumps.collection_offer,
+            buyer: self.buyer.key(),
+            id,
+            filter,
+            price,
+            remaining: quantity,
+        });
+
+        self.deposit_funds(amount)?;
+
+        Ok(())
+    }
+
+    pub fn deposit_funds(&mut self, amount: u64) -> Result<()> {
+        let cpi_accounts = Transfer {
+            from: self.buyer.to_account_info(),
+            to: self.collection_offer.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+        transfer(cpi_ctx, amount)?;
+
+        Ok(())
+    }
+}
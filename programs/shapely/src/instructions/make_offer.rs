@@ -0,0 +1,84 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::{
+    metadata::{Metadata, MetadataAccount},
+    token::Mint,
+};
+
+use crate::{
+    error::ShapelyError,
+    state::{Config, Offer},
+};
+
+#[derive(Accounts)]
+pub struct MakeOffer<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub accessory_mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [
+            b"metadata".as_ref(),
+            metadata_program.key().as_ref(),
+            accessory_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub accessory_metadata: Account<'info, MetadataAccount>,
+
+    #[account(
+        init,
+        payer = buyer,
+        seeds = [b"offer", accessory_mint.key().as_ref(), buyer.key().as_ref()],
+        bump,
+        space = Offer::SPACE
+    )]
+    pub offer: Account<'info, Offer>,
+
+    #[account(seeds = [b"config", config.seed.to_le_bytes().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    pub metadata_program: Program<'info, Metadata>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MakeOffer<'info> {
+    pub fn make_offer(&mut self, amount: u64, bumps: &MakeOfferBumps) -> Result<()> {
+        self.config
+            .verify_accessory(&self.accessory_metadata, &self.accessory_mint.key())?;
+        require!(amount > 0, ShapelyError::InvalidOfferAmount);
+
+        self.initialize_offer(amount, bumps.offer)?;
+        self.deposit_funds(amount)?;
+        Ok(())
+    }
+
+    pub fn initialize_offer(&mut self, amount: u64, bump: u8) -> Result<()> {
+        self.offer.set_inner(Offer {
+            bump,
+            buyer: self.buyer.key(),
+            accessory_mint: self.accessory_mint.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+
+    pub fn deposit_funds(&mut self, amount: u64) -> Result<()> {
+        let cpi_accounts = Transfer {
+            from: self.buyer.to_account_info(),
+            to: self.offer.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+        transfer(cpi_ctx, amount)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::{create, get_associated_token_address, AssociatedToken, Create},
+    metadata::MetadataAccount,
+    token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+use crate::{
+    error::ShapelyError,
+    state::{Config, SwapOffer},
+};
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct MakeSwap<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(
+        init,
+        payer = maker,
+        seeds = [b"swap", maker.key().as_ref(), id.to_le_bytes().as_ref()],
+        bump,
+        space = SwapOffer::SPACE
+    )]
+    pub swap: Account<'info, SwapOffer>,
+
+    #[account(seeds = [b"config", config.seed.to_le_bytes().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MakeSwap<'info> {
+    /// Remaining accounts hold one group per offered accessory:
+    /// `[accessory_mint, accessory_metadata, maker_accessory_ata (mut), swap_vault (mut)]`
+    pub fn make_swap(
+        &mut self,
+        id: u64,
+        requested_mints: Vec<Pubkey>,
+        sol_top_up: u64,
+        remaining_accounts: &'info [AccountInfo<'info>],
+        bumps: &MakeSwapBumps,
+    ) -> Result<()> {
+        let offered_count = remaining_accounts.len() / 4;
+
+        require!(
+            offered_count > 0
+                && offered_count * 4 == remaining_accounts.len()
+                && offered_count <= SwapOffer::MAX_ACCESSORIES,
+            ShapelyError::InvalidSwapAccounts
+        );
+        require!(
+            (!requested_mints.is_empty() || sol_top_up > 0)
+                && requested_mints.len() <= SwapOffer::MAX_ACCESSORIES,
+            ShapelyError::InvalidSwapTerms
+        );
+
+        let mut offered_mints = Vec::with_capacity(offered_count);
+
+        for accounts in remaining_accounts.chunks(4) {
+            let accessory_mint = self.deposit_accessory(accounts)?;
+
+            require!(
+                !offered_mints.contains(&accessory_mint),
+                ShapelyError::InvalidSwapAccounts
+            );
+            offered_mints.push(accessory_mint);
+        }
+
+        self.swap.set_inner(SwapOffer {
+            bump: bumps.swap,
+            maker: self.maker.key(),
+            config: self.config.key(),
+            id,
+            offered_mints,
+            requested_mints,
+            sol_top_up,
+        });
+
+        Ok(())
+    }
+
+    pub fn deposit_accessory(&mut self, accounts: &'info [AccountInfo<'info>]) -> Result<Pubkey> {
+        let [accessory_mint_info, accessory_metadata_info, maker_ata_info, swap_vault_info] =
+            accounts
+        else {
+            return err!(ShapelyError::InvalidSwapAccounts);
+        };
+
+        let accessory_mint = Account::<Mint>::try_from(accessory_mint_info)?;
+        let accessory_metadata = Account::<MetadataAccount>::try_from(accessory_metadata_info)?;
+        let maker_ata = Account::<TokenAccount>::try_from(maker_ata_info)?;
+
+        self.config
+            .verify_accessory(&accessory_metadata, &accessory_mint.key())?;
+
+        require_keys_eq!(
+            maker_ata.key(),
+            get_associated_token_address(&self.maker.key(), &accessory_mint.key()),
+            ShapelyError::InvalidSwapAccounts
+        );
+        require!(maker_ata.amount == 1, ShapelyError::InvalidSwapAccounts);
+        require_keys_eq!(
+            swap_vault_info.key(),
+            get_associated_token_address(&self.swap.key(), &accessory_mint.key()),
+            ShapelyError::InvalidSwapAccounts
+        );
+
+        let cpi_accounts = Create {
+            payer: self.maker.to_account_info(),
+            associated_token: swap_vault_info.clone(),
+            authority: self.swap.to_account_info(),
+            mint: accessory_mint_info.clone(),
+            system_program: self.system_program.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            cpi_accounts,
+        );
+
+        create(cpi_ctx)?;
+
+        let cpi_accounts = TransferChecked {
+            from: maker_ata_info.clone(),
+            mint: accessory_mint_info.clone(),
+            to: swap_vault_info.clone(),
+            authority: self.maker.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+
+        transfer_checked(cpi_ctx, maker_ata.amount, accessory_mint.decimals)?;
+
+        Ok(accessory_mint.key())
+    }
+}
@@ -0,0 +1,93 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{
+    error::ShapelyError,
+    program::Shapely,
+    state::{AvatarPolicy, Config, LegacyConfig},
+};
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct MigrateConfig<'info> {
+    #[account(mut)]
+    pub upgrade_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", seed.to_le_bytes().as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    /// CHECK: Config created before the layout changed, it no longer deserializes as a `Config` and is checked in the handler
+    pub config: UncheckedAccount<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ ShapelyError::Unauthorized)]
+    pub program: Program<'info, Shapely>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(upgrade_authority.key()) @ ShapelyError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateConfig<'info> {
+    /// Grows a legacy config to the current layout, with the upgrade authority as its admin
+    pub fn migrate_config(&mut self) -> Result<()> {
+        let config = self.config.to_account_info();
+
+        let legacy = {
+            let data = config.try_borrow_data()?;
+            require!(
+                data.len() == LegacyConfig::SPACE && data[..8] == *Config::DISCRIMINATOR,
+                ShapelyError::ConfigAlreadyMigrated
+            );
+
+            LegacyConfig::deserialize(&mut &data[8..])?
+        };
+
+        let rent = Rent::get()?
+            .minimum_balance(Config::SPACE)
+            .saturating_sub(config.lamports());
+        if rent > 0 {
+            let cpi_program = self.system_program.to_account_info();
+            let cpi_accounts = Transfer {
+                from: self.upgrade_authority.to_account_info(),
+                to: config.clone(),
+            };
+            transfer(CpiContext::new(cpi_program, cpi_accounts), rent)?;
+        }
+
+        config.resize(Config::SPACE)?;
+
+        let migrated = Config {
+            bump: legacy.bump,
+            treasury_bump: legacy.treasury_bump,
+            avatar_collection_bump: legacy.avatar_collection_bump,
+            accessory_collection_bump: legacy.accessory_collection_bump,
+            avatar_collection: legacy.avatar_collection,
+            accessory_collection: legacy.accessory_collection,
+            fee: legacy.fee,
+            seed: legacy.seed,
+            admin: self.upgrade_authority.key(),
+            metadata_updates_enabled: true,
+            avatar_drop_active: false,
+            accessory_drop_active: false,
+            accessory_mint_cap: 0,
+            mint_cap_period: 0,
+            swap_fee: 0,
+            referral_bps: 0,
+            avatar_policy: AvatarPolicy::Required,
+            payment_mints: Vec::new(),
+            avatar_drop_id: 0,
+            referrers: Vec::new(),
+        };
+
+        let mut data = config.try_borrow_mut_data()?;
+        migrated.try_serialize(&mut &mut data[..])
+    }
+}
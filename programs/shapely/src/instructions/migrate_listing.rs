@@ -0,0 +1,76 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::token::Mint;
+
+use crate::{
+    error::ShapelyError,
+    state::{LegacyListing, Listing},
+};
+
+#[derive(Accounts)]
+pub struct MigrateListing<'info> {
+    #[account(mut)]
+    pub artist: Signer<'info>,
+
+    pub accessory_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"listing", accessory_mint.key().as_ref(), artist.key().as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    /// CHECK: Listing created before the layout changed, it no longer deserializes as a `Listing` and is checked in the handler
+    pub listing: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateListing<'info> {
+    /// Grows a legacy listing to the current layout as a fixed price SOL listing, so it can be bought,
+    /// delisted or expired again
+    pub fn migrate_listing(&mut self) -> Result<()> {
+        let listing = self.listing.to_account_info();
+
+        let legacy = {
+            let data = listing.try_borrow_data()?;
+            require!(
+                data.len() == LegacyListing::SPACE && data[..8] == *Listing::DISCRIMINATOR,
+                ShapelyError::ListingAlreadyMigrated
+            );
+
+            LegacyListing::deserialize(&mut &data[8..])?
+        };
+
+        let rent = Rent::get()?
+            .minimum_balance(Listing::SPACE)
+            .saturating_sub(listing.lamports());
+        if rent > 0 {
+            let cpi_program = self.system_program.to_account_info();
+            let cpi_accounts = Transfer {
+                from: self.artist.to_account_info(),
+                to: listing.clone(),
+            };
+            transfer(CpiContext::new(cpi_program, cpi_accounts), rent)?;
+        }
+
+        listing.resize(Listing::SPACE)?;
+
+        let migrated = Listing {
+            bump: legacy.bump,
+            price: legacy.price,
+            accessory_mint: legacy.accessory_mint,
+            payment_mint: None,
+            expires_at: None,
+            dutch_auction: None,
+            delegated: false,
+            claimable_proceeds: false,
+            reserved_buyer: None,
+        };
+
+        let mut data = listing.try_borrow_mut_data()?;
+        migrated.try_serialize(&mut &mut data[..])
+    }
+}
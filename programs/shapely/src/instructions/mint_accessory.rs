@@ -18,7 +18,11 @@ use anchor_spl::{
     token::{mint_to, Mint, MintTo, Token, TokenAccount},
 };
 
-use crate::{error::ShapelyError, state::Config};
+use crate::{
+    constants::MAX_ROYALTY_BPS,
+    error::ShapelyError,
+    state::{AccessoryRecord, Config, CreatorShare, MintCounter, MintDrop},
+};
 
 #[derive(Accounts)]
 #[instruction(name: String)]
@@ -43,6 +47,24 @@ pub struct MintAccessory<'info> {
     )]
     pub artist_accessory_ata: Account<'info, TokenAccount>,
 
+    #[account(
+        init,
+        payer = artist,
+        seeds = [b"accessory", accessory_mint.key().as_ref()],
+        bump,
+        space = AccessoryRecord::SPACE
+    )]
+    pub accessory_record: Account<'info, AccessoryRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = artist,
+        seeds = [b"mint counter", config.key().as_ref(), artist.key().as_ref()],
+        bump,
+        space = MintCounter::SPACE
+    )]
+    pub mint_counter: Account<'info, MintCounter>,
+
     #[account(
         mut,
         seeds = [
@@ -86,6 +108,13 @@ pub struct MintAccessory<'info> {
     #[account(seeds = [b"config", config.seed.to_le_bytes().as_ref()], bump = config.bump)]
     pub config: Account<'info, Config>,
 
+    #[account(
+        mut,
+        seeds = ["accessory drop".as_bytes(), config.key().as_ref()],
+        bump = accessory_drop.bump
+    )]
+    pub accessory_drop: Option<Account<'info, MintDrop>>,
+
     #[account(address = INSTRUCTIONS_SYSVAR_PROGRAM_ID)]
     /// CHECK: Sysvar instruction account that is being checked with an address constraint
     pub sysvar_instruction: UncheckedAccount<'info>,
@@ -100,14 +129,42 @@ pub struct MintAccessory<'info> {
 }
 
 impl<'info> MintAccessory<'info> {
-    pub fn mint_accessory(&mut self, name: String, uri: String) -> Result<()> {
+    pub fn mint_accessory(
+        &mut self,
+        name: String,
+        uri: String,
+        royalty_bps: u16,
+        creators: Vec<CreatorShare>,
+        slot: u8,
+        bumps: &MintAccessoryBumps,
+    ) -> Result<()> {
+        require!(royalty_bps <= MAX_ROYALTY_BPS, ShapelyError::RoyaltyTooHigh);
+
+        // Solo accessories pay everything to the artist
+        let creators = if creators.is_empty() {
+            vec![CreatorShare {
+                address: self.artist.key(),
+                share: 100,
+            }]
+        } else {
+            creators
+        };
+
+        AccessoryRecord::validate_creators(&creators)?;
+
         let config_seed_bytes = self.config.seed.to_le_bytes();
         let seeds = &[b"config", config_seed_bytes.as_ref(), &[self.config.bump]];
         let signer_seeds = &[&seeds[..]];
 
+        self.record_drop_mint(&uri)?;
+
+        self.record_artist_mint(bumps.mint_counter)?;
+
+        self.initialize_accessory_record(royalty_bps, creators, slot, bumps.accessory_record)?;
+
         self.mint_accessory_nft(signer_seeds)?;
 
-        self.create_accessory_metadata(name, uri, signer_seeds)?;
+        self.create_accessory_metadata(name, uri, royalty_bps, signer_seeds)?;
 
         self.create_accessory_master_edition(signer_seeds)?;
 
@@ -116,6 +173,53 @@ impl<'info> MintAccessory<'info> {
         Ok(())
     }
 
+    pub fn record_artist_mint(&mut self, bump: u8) -> Result<()> {
+        if !self.mint_counter.is_initialized() {
+            self.mint_counter.set_inner(MintCounter {
+                bump,
+                config: self.config.key(),
+                artist: self.artist.key(),
+                period_start: Clock::get()?.unix_timestamp,
+                minted: 0,
+                cap_override: None,
+            });
+        }
+
+        self.mint_counter
+            .record_mint(self.config.accessory_mint_cap, self.config.mint_cap_period)
+    }
+
+    pub fn initialize_accessory_record(
+        &mut self,
+        royalty_bps: u16,
+        creators: Vec<CreatorShare>,
+        slot: u8,
+        bump: u8,
+    ) -> Result<()> {
+        self.accessory_record.set_inner(AccessoryRecord {
+            bump,
+            accessory_mint: self.accessory_mint.key(),
+            artist: self.artist.key(),
+            slot,
+            royalty_bps,
+            metadata_locked: false,
+            creators,
+        });
+
+        Ok(())
+    }
+
+    pub fn record_drop_mint(&mut self, uri: &str) -> Result<()> {
+        if !self.config.accessory_drop_active {
+            return Ok(());
+        }
+
+        self.accessory_drop
+            .as_mut()
+            .ok_or(ShapelyError::MissingDrop)?
+            .record_mint(uri, &Clock::get()?)
+    }
+
     pub fn mint_accessory_nft(&mut self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
         let cpi_program = self.token_program.to_account_info();
 
@@ -136,6 +240,7 @@ impl<'info> MintAccessory<'info> {
         &mut self,
         name: String,
         uri: String,
+        royalty_bps: u16,
         signer_seeds: &[&[&[u8]]],
     ) -> Result<()> {
         let metadata = &self.accessory_metadata.to_account_info();
@@ -167,7 +272,7 @@ impl<'info> MintAccessory<'info> {
                     name,
                     symbol: "SACCESSORY".to_owned(),
                     uri,
-                    seller_fee_basis_points: 0,
+                    seller_fee_basis_points: royalty_bps,
                     creators: Some(creator),
                     collection: Some(Collection {
                         verified: false,
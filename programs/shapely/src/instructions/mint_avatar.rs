@@ -18,7 +18,10 @@ use anchor_spl::{
     token::{mint_to, Mint, MintTo, Token, TokenAccount},
 };
 
-use crate::{error::ShapelyError, state::Config};
+use crate::{
+    error::ShapelyError,
+    state::{AllowlistClaim, Config, MintDrop},
+};
 
 #[derive(Accounts)]
 pub struct MintAvatar<'info> {
@@ -87,6 +90,27 @@ pub struct MintAvatar<'info> {
     #[account(seeds = [b"config", config.seed.to_le_bytes().as_ref()], bump = config.bump)]
     pub config: Account<'info, Config>,
 
+    #[account(
+        mut,
+        seeds = ["avatar drop".as_bytes(), config.key().as_ref()],
+        bump = avatar_drop.bump
+    )]
+    pub avatar_drop: Option<Account<'info, MintDrop>>,
+
+    #[account(
+        init,
+        payer = collector,
+        seeds = [
+            b"allowlist claim",
+            config.key().as_ref(),
+            config.avatar_drop_id.to_le_bytes().as_ref(),
+            collector.key().as_ref(),
+        ],
+        bump,
+        space = AllowlistClaim::SPACE
+    )]
+    pub allowlist_claim: Option<Account<'info, AllowlistClaim>>,
+
     #[account(address = INSTRUCTIONS_SYSVAR_PROGRAM_ID)]
     /// CHECK: Sysvar instruction account that is being checked with an address constraint
     pub sysvar_instruction: UncheckedAccount<'info>,
@@ -101,11 +125,19 @@ pub struct MintAvatar<'info> {
 }
 
 impl<'info> MintAvatar<'info> {
-    pub fn mint_avatar(&mut self, name: String, uri: String) -> Result<()> {
+    pub fn mint_avatar(
+        &mut self,
+        name: String,
+        uri: String,
+        proof: Vec<[u8; 32]>,
+        bumps: &MintAvatarBumps,
+    ) -> Result<()> {
         let config_seed_bytes = self.config.seed.to_le_bytes();
         let seeds = &[b"config", config_seed_bytes.as_ref(), &[self.config.bump]];
         let signer_seeds = &[&seeds[..]];
 
+        self.record_drop_mint(&uri, &proof, bumps)?;
+
         self.mint_avatar_nft(signer_seeds)?;
 
         self.create_avatar_metadata(name, uri, signer_seeds)?;
@@ -117,6 +149,43 @@ impl<'info> MintAvatar<'info> {
         Ok(())
     }
 
+    pub fn record_drop_mint(
+        &mut self,
+        uri: &str,
+        proof: &[[u8; 32]],
+        bumps: &MintAvatarBumps,
+    ) -> Result<()> {
+        if !self.config.avatar_drop_active {
+            return Ok(());
+        }
+
+        let avatar_drop = self.avatar_drop.as_mut().ok_or(ShapelyError::MissingDrop)?;
+
+        avatar_drop.record_mint(uri, &Clock::get()?)?;
+
+        if avatar_drop.allowlist_root.is_none() {
+            return Ok(());
+        }
+
+        avatar_drop.verify_allowlist(&self.collector.key(), proof)?;
+
+        // The claim account can only be initialized once per wallet and drop
+        let allowlist_claim = self
+            .allowlist_claim
+            .as_mut()
+            .ok_or(ShapelyError::MissingAllowlistClaim)?;
+
+        allowlist_claim.set_inner(AllowlistClaim {
+            bump: bumps.allowlist_claim.unwrap_or_default(),
+            config: self.config.key(),
+            drop_id: self.config.avatar_drop_id,
+            wallet: self.collector.key(),
+            claimed_at: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
     pub fn mint_avatar_nft(&mut self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
         let cpi_program = self.token_program.to_account_info();
 
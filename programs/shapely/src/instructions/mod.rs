@@ -1,13 +1,83 @@
+pub mod accept_offer;
+pub mod backfill_accessory_record;
 pub mod buy_accessory;
+pub mod buy_bundle;
+pub mod buy_many;
+pub mod cancel_collection_offer;
+pub mod cancel_offer;
+pub mod cancel_swap;
+pub mod claim_proceeds;
+pub mod close_drop;
+pub mod create_auction;
+pub mod create_drop;
 pub mod delist_accessory;
+pub mod delist_bundle;
+pub mod delist_rental;
+pub mod expire_listing;
+pub mod fill_collection_offer;
+pub mod fill_swap;
+pub mod get_listing_price;
+pub mod gift_accessory;
 pub mod initialize;
 pub mod list_accessory;
+pub mod list_accessory_delegated;
+pub mod list_bundle;
+pub mod list_rental;
+pub mod make_collection_offer;
+pub mod make_offer;
+pub mod make_swap;
+pub mod migrate_config;
+pub mod migrate_listing;
 pub mod mint_accessory;
 pub mod mint_avatar;
+pub mod place_bid;
+pub mod reclaim_rental;
+pub mod rent_accessory;
+pub mod set_artist_mint_cap;
+pub mod set_drop_allowlist;
+pub mod settle_auction;
+pub mod update_accessory_metadata;
+pub mod update_config;
+pub mod update_listing_price;
 
+pub use accept_offer::*;
+pub use backfill_accessory_record::*;
 pub use buy_accessory::*;
+pub use buy_bundle::*;
+pub use buy_many::*;
+pub use cancel_collection_offer::*;
+pub use cancel_offer::*;
+pub use cancel_swap::*;
+pub use claim_proceeds::*;
+pub use close_drop::*;
+pub use create_auction::*;
+pub use create_drop::*;
 pub use delist_accessory::*;
+pub use delist_bundle::*;
+pub use delist_rental::*;
+pub use expire_listing::*;
+pub use fill_collection_offer::*;
+pub use fill_swap::*;
+pub use get_listing_price::*;
+pub use gift_accessory::*;
 pub use initialize::*;
 pub use list_accessory::*;
+pub use list_accessory_delegated::*;
+pub use list_bundle::*;
+pub use list_rental::*;
+pub use make_collection_offer::*;
+pub use make_offer::*;
+pub use make_swap::*;
+pub use migrate_config::*;
+pub use migrate_listing::*;
 pub use mint_accessory::*;
 pub use mint_avatar::*;
+pub use place_bid::*;
+pub use reclaim_rental::*;
+pub use rent_accessory::*;
+pub use set_artist_mint_cap::*;
+pub use set_drop_allowlist::*;
+pub use settle_auction::*;
+pub use update_accessory_metadata::*;
+pub use update_config::*;
+pub use update_listing_price::*;
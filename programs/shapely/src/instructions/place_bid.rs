@@ -0,0 +1,126 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::{
+    metadata::{Metadata, MetadataAccount},
+    token::Mint,
+};
+
+use crate::{
+    error::ShapelyError,
+    payment::transfer_lamports,
+    state::{AccessoryRecord, Auction, Config},
+};
+
+#[derive(Accounts)]
+pub struct PlaceBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    /// The avatar accounts are only needed when the config avatar policy requires it
+    #[account(
+        seeds = [b"avatar", bidder.key().as_ref(), config.avatar_collection.key().as_ref()],
+        bump,
+    )]
+    pub bidder_avatar_mint: Option<Account<'info, Mint>>,
+
+    /// Validated against the avatar mint and collection in the handler
+    pub bidder_avatar_metadata: Option<Account<'info, MetadataAccount>>,
+
+    /// CHECK: This will be validated in the auction seeds
+    pub seller: AccountInfo<'info>,
+
+    pub accessory_mint: Account<'info, Mint>,
+
+    #[account(seeds = [b"accessory", accessory_mint.key().as_ref()], bump = accessory_record.bump)]
+    pub accessory_record: Account<'info, AccessoryRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"auction", accessory_mint.key().as_ref(), seller.key().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, Auction>,
+
+    #[account(mut)]
+    /// CHECK: This is validated against the highest bidder stored in the auction
+    pub previous_bidder: Option<AccountInfo<'info>>,
+
+    #[account(seeds = [b"config", config.seed.to_le_bytes().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    pub metadata_program: Program<'info, Metadata>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PlaceBid<'info> {
+    pub fn place_bid(&mut self, amount: u64) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp < self.auction.end_time,
+            ShapelyError::AuctionEnded
+        );
+
+        self.verify_bidder_avatar()?;
+
+        let min_bid = self.auction.min_bid().ok_or(ShapelyError::MathOverflow)?;
+        require!(amount >= min_bid, ShapelyError::BidTooLow);
+
+        self.refund_previous_bidder()?;
+        self.deposit_bid(amount)?;
+
+        self.auction.highest_bidder = Some(self.bidder.key());
+        self.auction.highest_bid = amount;
+
+        Ok(())
+    }
+
+    pub fn verify_bidder_avatar(&self) -> Result<()> {
+        let primary_sale = self.seller.key() == self.accessory_record.artist;
+        let avatar_mint = self.bidder_avatar_mint.as_ref().map(|mint| mint.key());
+        let avatar = self
+            .bidder_avatar_metadata
+            .as_ref()
+            .zip(avatar_mint.as_ref())
+            .map(|(metadata, mint)| (&**metadata, mint));
+
+        self.config.verify_buyer_avatar(avatar, primary_sale)
+    }
+
+    pub fn refund_previous_bidder(&mut self) -> Result<()> {
+        let Some(highest_bidder) = self.auction.highest_bidder else {
+            return Ok(());
+        };
+
+        let previous_bidder = self
+            .previous_bidder
+            .as_ref()
+            .ok_or(ShapelyError::InvalidPreviousBidder)?;
+
+        require_keys_eq!(
+            previous_bidder.key(),
+            highest_bidder,
+            ShapelyError::InvalidPreviousBidder
+        );
+
+        transfer_lamports(
+            &self.auction.to_account_info(),
+            previous_bidder,
+            self.auction.highest_bid,
+        )
+    }
+
+    pub fn deposit_bid(&mut self, amount: u64) -> Result<()> {
+        let cpi_accounts = Transfer {
+            from: self.bidder.to_account_info(),
+            to: self.auction.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+        transfer(cpi_ctx, amount)?;
+
+        Ok(())
+    }
+}
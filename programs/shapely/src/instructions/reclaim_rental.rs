@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    metadata::{
+        mpl_token_metadata::instructions::{
+            ThawDelegatedAccountCpi, ThawDelegatedAccountCpiAccounts,
+        },
+        MasterEditionAccount, Metadata,
+    },
+    token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+use crate::{error::ShapelyError, state::Rental};
+
+#[derive(Accounts)]
+pub struct ReclaimRental<'info> {
+    pub cranker: Signer<'info>,
+
+    /// CHECK: This will be validated in the rental seeds
+    pub owner: AccountInfo<'info>,
+
+    /// CHECK: This is validated against the renter stored in the rental
+    pub renter: AccountInfo<'info>,
+
+    pub accessory_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"rental", accessory_mint.key().as_ref(), owner.key().as_ref()],
+        bump = rental.bump,
+        constraint = rental.renter == Some(renter.key()) @ ShapelyError::InvalidRenter
+    )]
+    pub rental: Account<'info, Rental>,
+
+    #[account(
+        mut,
+        associated_token::mint = accessory_mint,
+        associated_token::authority = rental
+    )]
+    pub rental_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = accessory_mint,
+        associated_token::authority = renter
+    )]
+    pub renter_accessory_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [
+            b"metadata".as_ref(),
+            metadata_program.key().as_ref(),
+            accessory_mint.key().as_ref(),
+            b"edition".as_ref(),
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub accessory_master_edition: Account<'info, MasterEditionAccount>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub metadata_program: Program<'info, Metadata>,
+}
+
+impl<'info> ReclaimRental<'info> {
+    pub fn reclaim_rental(&mut self) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp >= self.rental.rented_until,
+            ShapelyError::RentalNotEnded
+        );
+
+        let seeds = &[
+            b"rental",
+            self.accessory_mint.to_account_info().key.as_ref(),
+            self.owner.to_account_info().key.as_ref(),
+            &[self.rental.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        self.thaw_nft(signer_seeds)?;
+        self.return_nft(signer_seeds)?;
+
+        self.rental.renter = None;
+        self.rental.rented_until = 0;
+
+        Ok(())
+    }
+
+    pub fn thaw_nft(&mut self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let metadata_program = &self.metadata_program.to_account_info();
+        let rental = &self.rental.to_account_info();
+        let renter_accessory_ata = &self.renter_accessory_ata.to_account_info();
+        let accessory_master_edition = &self.accessory_master_edition.to_account_info();
+        let accessory_mint = &self.accessory_mint.to_account_info();
+        let token_program = &self.token_program.to_account_info();
+
+        ThawDelegatedAccountCpi::new(
+            metadata_program,
+            ThawDelegatedAccountCpiAccounts {
+                delegate: rental,
+                token_account: renter_accessory_ata,
+                edition: accessory_master_edition,
+                mint: accessory_mint,
+                token_program,
+            },
+        )
+        .invoke_signed(signer_seeds)?;
+
+        Ok(())
+    }
+
+    /// Pulls the accessory back into the rental vault using the delegation set up when renting
+    pub fn return_nft(&mut self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked {
+            from: self.renter_accessory_ata.to_account_info(),
+            mint: self.accessory_mint.to_account_info(),
+            to: self.rental_vault.to_account_info(),
+            authority: self.rental.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        transfer_checked(
+            cpi_ctx,
+            self.renter_accessory_ata.delegated_amount,
+            self.accessory_mint.decimals,
+        )?;
+
+        Ok(())
+    }
+}
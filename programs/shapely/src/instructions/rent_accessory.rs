@@ -0,0 +1,198 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{
+        mpl_token_metadata::instructions::{
+            FreezeDelegatedAccountCpi, FreezeDelegatedAccountCpiAccounts,
+        },
+        MasterEditionAccount, Metadata,
+    },
+    token::{approve, transfer_checked, Approve, Mint, Token, TokenAccount, TransferChecked},
+};
+
+use crate::{
+    error::ShapelyError,
+    payment::SaleSplit,
+    state::{Config, Rental},
+};
+
+#[derive(Accounts)]
+pub struct RentAccessory<'info> {
+    #[account(mut)]
+    pub renter: Signer<'info>,
+
+    #[account(mut)]
+    /// CHECK: This will be validated in the rental seeds
+    pub owner: AccountInfo<'info>,
+
+    pub accessory_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"rental", accessory_mint.key().as_ref(), owner.key().as_ref()],
+        bump = rental.bump,
+        constraint = rental.renter.is_none() @ ShapelyError::AccessoryRented
+    )]
+    pub rental: Account<'info, Rental>,
+
+    #[account(
+        mut,
+        associated_token::mint = accessory_mint,
+        associated_token::authority = rental
+    )]
+    pub rental_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = renter,
+        associated_token::mint = accessory_mint,
+        associated_token::authority = renter
+    )]
+    pub renter_accessory_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [
+            b"metadata".as_ref(),
+            metadata_program.key().as_ref(),
+            accessory_mint.key().as_ref(),
+            b"edition".as_ref(),
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub accessory_master_edition: Account<'info, MasterEditionAccount>,
+
+    #[account(seeds = [b"config", config.seed.to_le_bytes().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"treasury", config.key().as_ref()], bump = config.treasury_bump)]
+    pub treasury: SystemAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub metadata_program: Program<'info, Metadata>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RentAccessory<'info> {
+    pub fn rent_accessory(&mut self, periods: u32, expected_price_per_period: u64) -> Result<()> {
+        require!(
+            self.rental.price_per_period == expected_price_per_period,
+            ShapelyError::UnexpectedPrice
+        );
+
+        let (price, rented_until) = self.rental.quote(periods, Clock::get()?.unix_timestamp)?;
+
+        self.rental.renter = Some(self.renter.key());
+        self.rental.rented_until = rented_until;
+
+        let seeds = &[
+            b"rental",
+            self.accessory_mint.to_account_info().key.as_ref(),
+            self.owner.to_account_info().key.as_ref(),
+            &[self.rental.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        self.process_payment(price)?;
+        self.lend_nft(signer_seeds)?;
+        self.approve_rental()?;
+        self.freeze_nft(signer_seeds)?;
+        Ok(())
+    }
+
+    pub fn process_payment(&mut self, price: u64) -> Result<()> {
+        let split = SaleSplit::new(price, self.config.fee, 0)?;
+
+        // 1. Collect fees
+        let cpi_accounts = Transfer {
+            from: self.renter.to_account_info(),
+            to: self.treasury.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+        transfer(cpi_ctx, split.fee)?;
+
+        // 2. Pay owner
+        let cpi_accounts = Transfer {
+            from: self.renter.to_account_info(),
+            to: self.owner.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+        transfer(cpi_ctx, split.seller)?;
+
+        Ok(())
+    }
+
+    pub fn lend_nft(&mut self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked {
+            from: self.rental_vault.to_account_info(),
+            mint: self.accessory_mint.to_account_info(),
+            to: self.renter_accessory_ata.to_account_info(),
+            authority: self.rental.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        transfer_checked(
+            cpi_ctx,
+            self.rental_vault.amount,
+            self.accessory_mint.decimals,
+        )?;
+
+        Ok(())
+    }
+
+    /// Lets the rental pull the accessory back once the rental ends
+    pub fn approve_rental(&mut self) -> Result<()> {
+        self.renter_accessory_ata.reload()?;
+
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = Approve {
+            to: self.renter_accessory_ata.to_account_info(),
+            delegate: self.rental.to_account_info(),
+            authority: self.renter.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        approve(cpi_ctx, self.renter_accessory_ata.amount)?;
+
+        Ok(())
+    }
+
+    pub fn freeze_nft(&mut self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let metadata_program = &self.metadata_program.to_account_info();
+        let rental = &self.rental.to_account_info();
+        let renter_accessory_ata = &self.renter_accessory_ata.to_account_info();
+        let accessory_master_edition = &self.accessory_master_edition.to_account_info();
+        let accessory_mint = &self.accessory_mint.to_account_info();
+        let token_program = &self.token_program.to_account_info();
+
+        FreezeDelegatedAccountCpi::new(
+            metadata_program,
+            FreezeDelegatedAccountCpiAccounts {
+                delegate: rental,
+                token_account: renter_accessory_ata,
+                edition: accessory_master_edition,
+                mint: accessory_mint,
+                token_program,
+            },
+        )
+        .invoke_signed(signer_seeds)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ShapelyError,
+    state::{Config, MintCounter},
+};
+
+#[derive(Accounts)]
+pub struct SetArtistMintCap<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub artist: SystemAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [b"mint counter", config.key().as_ref(), artist.key().as_ref()],
+        bump,
+        space = MintCounter::SPACE
+    )]
+    pub mint_counter: Account<'info, MintCounter>,
+
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+        has_one = admin @ ShapelyError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetArtistMintCap<'info> {
+    pub fn set_artist_mint_cap(
+        &mut self,
+        cap: Option<u32>,
+        bumps: &SetArtistMintCapBumps,
+    ) -> Result<()> {
+        if !self.mint_counter.is_initialized() {
+            self.mint_counter.set_inner(MintCounter {
+                bump: bumps.mint_counter,
+                config: self.config.key(),
+                artist: self.artist.key(),
+                period_start: Clock::get()?.unix_timestamp,
+                minted: 0,
+                cap_override: None,
+            });
+        }
+
+        self.mint_counter.cap_override = cap;
+
+        Ok(())
+    }
+}
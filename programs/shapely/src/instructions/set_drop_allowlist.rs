@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ShapelyError,
+    state::{Config, MintDrop},
+};
+
+#[derive(Accounts)]
+pub struct SetDropAllowlist<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [drop.kind.seed(), config.key().as_ref()],
+        bump = drop.bump
+    )]
+    pub drop: Account<'info, MintDrop>,
+
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+        has_one = admin @ ShapelyError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> SetDropAllowlist<'info> {
+    pub fn set_drop_allowlist(&mut self, allowlist_root: Option<[u8; 32]>) -> Result<()> {
+        self.drop.allowlist_root = allowlist_root;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,187 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{
+        close_account, transfer_checked, CloseAccount, Mint, Token, TokenAccount, TransferChecked,
+    },
+};
+
+use crate::{
+    error::ShapelyError,
+    payment::{transfer_lamports, SaleSplit},
+    state::{AccessoryRecord, Auction, Config},
+};
+
+#[derive(Accounts)]
+pub struct SettleAuction<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    #[account(mut)]
+    /// CHECK: This will be validated in the auction seeds
+    pub seller: AccountInfo<'info>,
+
+    #[account(
+        constraint = winner.key() == auction.highest_bidder.unwrap_or(auction.seller) @ ShapelyError::InvalidAuctionWinner
+    )]
+    /// CHECK: This is the highest bidder, or the seller when nobody placed a bid
+    pub winner: AccountInfo<'info>,
+
+    pub accessory_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"accessory", accessory_mint.key().as_ref()],
+        bump = accessory_record.bump
+    )]
+    pub accessory_record: Box<Account<'info, AccessoryRecord>>,
+
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = accessory_mint,
+        associated_token::authority = winner
+    )]
+    pub winner_accessory_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [b"auction", accessory_mint.key().as_ref(), seller.key().as_ref()],
+        bump = auction.bump,
+        constraint = Clock::get()?.unix_timestamp >= auction.end_time @ ShapelyError::AuctionNotEnded
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+
+    #[account(
+        mut,
+        associated_token::mint = accessory_mint,
+        associated_token::authority = auction
+    )]
+    pub auction_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(seeds = [b"config", config.seed.to_le_bytes().as_ref()], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(mut, seeds = [b"treasury", config.key().as_ref()], bump = config.treasury_bump)]
+    pub treasury: SystemAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SettleAuction<'info> {
+    /// Remaining accounts hold one `[creator (mut)]` per creator in the accessory record,
+    /// they are only paid when the auction had a bid
+    pub fn settle_auction(&mut self, creator_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        let seeds = &[
+            b"auction",
+            self.accessory_mint.to_account_info().key.as_ref(),
+            self.seller.to_account_info().key.as_ref(),
+            &[self.auction.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        // Without bids the accessory simply goes back to the seller
+        if self.auction.highest_bidder.is_some() {
+            self.process_payment(creator_accounts)?;
+            self.lock_metadata()?;
+        }
+
+        self.withdraw_nft(signer_seeds)?;
+        self.close_vault(signer_seeds)?;
+        Ok(())
+    }
+
+    pub fn process_payment(&mut self, creator_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        require!(
+            creator_accounts.len() == self.accessory_record.creators.len(),
+            ShapelyError::InvalidCreator
+        );
+
+        let seller = self.seller.key();
+        let royalty_bps = self.accessory_record.royalty_bps_for(&seller);
+
+        let split = SaleSplit::new(self.auction.highest_bid, self.config.fee, royalty_bps)?;
+
+        let (creator_amounts, seller_amount) =
+            self.accessory_record.creator_payouts(&seller, &split)?;
+
+        let auction = self.auction.to_account_info();
+
+        // 1. Collect fees
+        transfer_lamports(&auction, &self.treasury.to_account_info(), split.fee)?;
+
+        // 2. Pay creators their share of the sale or royalty
+        for ((creator, amount), creator_info) in self
+            .accessory_record
+            .creators
+            .iter()
+            .zip(creator_amounts)
+            .zip(creator_accounts)
+        {
+            require_keys_eq!(
+                creator_info.key(),
+                creator.address,
+                ShapelyError::InvalidCreator
+            );
+
+            if amount == 0 {
+                continue;
+            }
+
+            transfer_lamports(&auction, creator_info, amount)?;
+        }
+
+        // 3. Payout seller
+        transfer_lamports(&auction, &self.seller.to_account_info(), seller_amount)?;
+
+        Ok(())
+    }
+
+    pub fn lock_metadata(&mut self) -> Result<()> {
+        self.accessory_record.metadata_locked = true;
+
+        Ok(())
+    }
+
+    pub fn withdraw_nft(&mut self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked {
+            from: self.auction_vault.to_account_info(),
+            mint: self.accessory_mint.to_account_info(),
+            to: self.winner_accessory_ata.to_account_info(),
+            authority: self.auction.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        transfer_checked(
+            cpi_ctx,
+            self.auction_vault.amount,
+            self.accessory_mint.decimals,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn close_vault(&mut self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = CloseAccount {
+            account: self.auction_vault.to_account_info(),
+            destination: self.seller.to_account_info(),
+            authority: self.auction.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        close_account(cpi_ctx)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    metadata::{
+        mpl_token_metadata::{
+            instructions::{
+                UpdateMetadataAccountV2Cpi, UpdateMetadataAccountV2CpiAccounts,
+                UpdateMetadataAccountV2InstructionArgs,
+            },
+            types::DataV2,
+        },
+        Metadata, MetadataAccount,
+    },
+    token::Mint,
+};
+
+use crate::{
+    error::ShapelyError,
+    state::{AccessoryRecord, Config},
+};
+
+#[derive(Accounts)]
+pub struct UpdateAccessoryMetadata<'info> {
+    pub artist: Signer<'info>,
+
+    pub accessory_mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"accessory", accessory_mint.key().as_ref()],
+        bump = accessory_record.bump,
+        has_one = artist @ ShapelyError::NotAccessoryArtist,
+        constraint = !accessory_record.metadata_locked @ ShapelyError::MetadataLocked
+    )]
+    pub accessory_record: Account<'info, AccessoryRecord>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"metadata".as_ref(),
+            metadata_program.key().as_ref(),
+            accessory_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub accessory_metadata: Account<'info, MetadataAccount>,
+
+    #[account(
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+        constraint = config.metadata_updates_enabled @ ShapelyError::MetadataUpdatesDisabled
+    )]
+    pub config: Account<'info, Config>,
+
+    pub metadata_program: Program<'info, Metadata>,
+}
+
+impl<'info> UpdateAccessoryMetadata<'info> {
+    pub fn update_accessory_metadata(&mut self, name: String, uri: String) -> Result<()> {
+        let config_seed_bytes = self.config.seed.to_le_bytes();
+        let seeds = &[b"config", config_seed_bytes.as_ref(), &[self.config.bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let metadata = &self.accessory_metadata.to_account_info();
+        let update_authority = &self.config.to_account_info();
+        let metadata_program = &self.metadata_program.to_account_info();
+
+        // Only the name and uri change, everything else is carried over as is
+        let current = &self.accessory_metadata;
+
+        let update_metadata = UpdateMetadataAccountV2Cpi::new(
+            metadata_program,
+            UpdateMetadataAccountV2CpiAccounts {
+                metadata,
+                update_authority,
+            },
+            UpdateMetadataAccountV2InstructionArgs {
+                data: Some(DataV2 {
+                    name,
+                    symbol: current.symbol.trim_end_matches('\0').to_owned(),
+                    uri,
+                    seller_fee_basis_points: current.seller_fee_basis_points,
+                    creators: current.creators.clone(),
+                    collection: current.collection.clone(),
+                    uses: current.uses.clone(),
+                }),
+                new_update_authority: None,
+                primary_sale_happened: None,
+                is_mutable: None,
+            },
+        );
+        update_metadata.invoke_signed(signer_seeds)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ShapelyError,
+    payment::BPS_DENOMINATOR,
+    state::{AvatarPolicy, Config},
+};
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+        has_one = admin @ ShapelyError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> UpdateConfig<'info> {
+    pub fn set_metadata_updates(&mut self, enabled: bool) -> Result<()> {
+        self.config.metadata_updates_enabled = enabled;
+
+        Ok(())
+    }
+
+    pub fn set_accessory_mint_cap(&mut self, cap: u32, period: i64) -> Result<()> {
+        require!(period >= 0, ShapelyError::InvalidMintCapPeriod);
+
+        self.config.accessory_mint_cap = cap;
+        self.config.mint_cap_period = period;
+
+        Ok(())
+    }
+
+    pub fn set_payment_mints(&mut self, payment_mints: Vec<Pubkey>) -> Result<()> {
+        require!(
+            payment_mints.len() <= Config::MAX_PAYMENT_MINTS,
+            ShapelyError::TooManyPaymentMints
+        );
+
+        self.config.payment_mints = payment_mints;
+
+        Ok(())
+    }
+
+    pub fn set_referrers(&mut self, referrers: Vec<Pubkey>) -> Result<()> {
+        require!(
+            referrers.len() <= Config::MAX_REFERRERS,
+            ShapelyError::TooManyReferrers
+        );
+
+        self.config.referrers = referrers;
+
+        Ok(())
+    }
+
+    pub fn set_swap_fee(&mut self, swap_fee: u64) -> Result<()> {
+        self.config.swap_fee = swap_fee;
+
+        Ok(())
+    }
+
+    pub fn set_referral_bps(&mut self, referral_bps: u16) -> Result<()> {
+        require!(
+            referral_bps as u64 <= BPS_DENOMINATOR,
+            ShapelyError::InvalidReferralBps
+        );
+
+        self.config.referral_bps = referral_bps;
+
+        Ok(())
+    }
+
+    pub fn set_avatar_policy(&mut self, avatar_policy: AvatarPolicy) -> Result<()> {
+        self.config.avatar_policy = avatar_policy;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{error::ShapelyError, events::ListingPriceUpdated, state::Listing};
+
+#[derive(Accounts)]
+pub struct UpdateListingPrice<'info> {
+    pub artist: Signer<'info>,
+
+    pub accessory_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"listing", accessory_mint.key().as_ref(), artist.key().as_ref()],
+        bump = listing.bump,
+        constraint = listing.dutch_auction.is_none() @ ShapelyError::DutchAuctionRepricing
+    )]
+    pub listing: Account<'info, Listing>,
+}
+
+impl<'info> UpdateListingPrice<'info> {
+    pub fn update_listing_price(&mut self, price: u64) -> Result<()> {
+        let old_price = self.listing.price;
+        self.listing.price = price;
+
+        emit!(ListingPriceUpdated {
+            listing: self.listing.key(),
+            accessory_mint: self.accessory_mint.key(),
+            seller: self.artist.key(),
+            payment_mint: self.listing.payment_mint,
+            reserved_buyer: self.listing.reserved_buyer,
+            old_price,
+            new_price: price,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -1,6 +1,9 @@
 pub mod constants;
 pub mod error;
+pub mod events;
 pub mod instructions;
+pub mod merkle;
+pub mod payment;
 pub mod state;
 
 use anchor_lang::prelude::*;
@@ -20,23 +23,314 @@ pub mod shapely {
         ctx.accounts.initialize(seed, fee, &ctx.bumps)
     }
 
-    pub fn mint_accessory(ctx: Context<MintAccessory>, name: String, uri: String) -> Result<()> {
-        ctx.accounts.mint_accessory(name, uri)
+    pub fn mint_accessory(
+        ctx: Context<MintAccessory>,
+        name: String,
+        uri: String,
+        royalty_bps: u16,
+        creators: Vec<CreatorShare>,
+        slot: u8,
+    ) -> Result<()> {
+        ctx.accounts
+            .mint_accessory(name, uri, royalty_bps, creators, slot, &ctx.bumps)
     }
 
-    pub fn mint_avatar(ctx: Context<MintAvatar>, name: String, uri: String) -> Result<()> {
-        ctx.accounts.mint_avatar(name, uri)
+    pub fn mint_avatar(
+        ctx: Context<MintAvatar>,
+        name: String,
+        uri: String,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts.mint_avatar(name, uri, proof, &ctx.bumps)
     }
 
-    pub fn list_accessory(ctx: Context<ListAccessory>, price: u64) -> Result<()> {
-        ctx.accounts.list_accessory(price, &ctx.bumps)
+    pub fn list_accessory(
+        ctx: Context<ListAccessory>,
+        price: u64,
+        payment_mint: Option<Pubkey>,
+        expires_at: Option<i64>,
+        reserved_buyer: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts
+            .list_accessory(price, payment_mint, expires_at, reserved_buyer, &ctx.bumps)
+    }
+
+    pub fn list_accessory_dutch(
+        ctx: Context<ListAccessory>,
+        dutch_auction: DutchAuction,
+        payment_mint: Option<Pubkey>,
+        expires_at: Option<i64>,
+        reserved_buyer: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.list_accessory_dutch(
+            dutch_auction,
+            payment_mint,
+            expires_at,
+            reserved_buyer,
+            &ctx.bumps,
+        )
+    }
+
+    pub fn list_accessory_delegated(
+        ctx: Context<ListAccessoryDelegated>,
+        price: u64,
+        payment_mint: Option<Pubkey>,
+        expires_at: Option<i64>,
+        reserved_buyer: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.list_accessory_delegated(
+            price,
+            payment_mint,
+            expires_at,
+            reserved_buyer,
+            &ctx.bumps,
+        )
+    }
+
+    pub fn get_listing_price(ctx: Context<GetListingPrice>) -> Result<u64> {
+        ctx.accounts.get_listing_price()
     }
 
     pub fn delist_accessory(ctx: Context<DelistAccessory>) -> Result<()> {
         ctx.accounts.delist_accessory()
     }
 
-    pub fn buy_accessory(ctx: Context<BuyAccessory>) -> Result<()> {
-        ctx.accounts.buy_accessory()
+    pub fn buy_accessory<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuyAccessory<'info>>,
+        expected_price: u64,
+        expected_payment_mint: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.buy_accessory(
+            expected_price,
+            expected_payment_mint,
+            ctx.remaining_accounts,
+        )
+    }
+
+    pub fn buy_many<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuyMany<'info>>,
+        max_total_price: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .buy_many(max_total_price, ctx.remaining_accounts)
+    }
+
+    pub fn claim_proceeds(ctx: Context<ClaimProceeds>) -> Result<()> {
+        ctx.accounts.claim_proceeds()
+    }
+
+    pub fn list_bundle<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ListBundle<'info>>,
+        id: u64,
+        price: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .list_bundle(id, price, ctx.remaining_accounts, &ctx.bumps)
+    }
+
+    pub fn delist_bundle<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DelistBundle<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.delist_bundle(ctx.remaining_accounts)
+    }
+
+    pub fn buy_bundle<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuyBundle<'info>>,
+        expected_price: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .buy_bundle(expected_price, ctx.remaining_accounts)
+    }
+
+    pub fn make_swap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MakeSwap<'info>>,
+        id: u64,
+        requested_mints: Vec<Pubkey>,
+        sol_top_up: u64,
+    ) -> Result<()> {
+        ctx.accounts.make_swap(
+            id,
+            requested_mints,
+            sol_top_up,
+            ctx.remaining_accounts,
+            &ctx.bumps,
+        )
+    }
+
+    pub fn cancel_swap<'info>(ctx: Context<'_, '_, 'info, 'info, CancelSwap<'info>>) -> Result<()> {
+        ctx.accounts.cancel_swap(ctx.remaining_accounts)
+    }
+
+    pub fn fill_swap<'info>(ctx: Context<'_, '_, 'info, 'info, FillSwap<'info>>) -> Result<()> {
+        ctx.accounts.fill_swap(ctx.remaining_accounts)
+    }
+
+    pub fn list_rental(
+        ctx: Context<ListRental>,
+        price_per_period: u64,
+        period: i64,
+        max_periods: u32,
+    ) -> Result<()> {
+        ctx.accounts
+            .list_rental(price_per_period, period, max_periods, &ctx.bumps)
+    }
+
+    pub fn delist_rental(ctx: Context<DelistRental>) -> Result<()> {
+        ctx.accounts.delist_rental()
+    }
+
+    pub fn rent_accessory(
+        ctx: Context<RentAccessory>,
+        periods: u32,
+        expected_price_per_period: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .rent_accessory(periods, expected_price_per_period)
+    }
+
+    pub fn reclaim_rental(ctx: Context<ReclaimRental>) -> Result<()> {
+        ctx.accounts.reclaim_rental()
+    }
+
+    pub fn gift_accessory(ctx: Context<GiftAccessory>, message: Option<String>) -> Result<()> {
+        ctx.accounts.gift_accessory(message)
+    }
+
+    pub fn create_auction(
+        ctx: Context<CreateAuction>,
+        reserve_price: u64,
+        min_increment: u64,
+        end_time: i64,
+    ) -> Result<()> {
+        ctx.accounts
+            .create_auction(reserve_price, min_increment, end_time, &ctx.bumps)
+    }
+
+    pub fn place_bid(ctx: Context<PlaceBid>, amount: u64) -> Result<()> {
+        ctx.accounts.place_bid(amount)
+    }
+
+    pub fn settle_auction<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleAuction<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.settle_auction(ctx.remaining_accounts)
+    }
+
+    pub fn make_offer(ctx: Context<MakeOffer>, amount: u64) -> Result<()> {
+        ctx.accounts.make_offer(amount, &ctx.bumps)
+    }
+
+    pub fn cancel_offer(ctx: Context<CancelOffer>) -> Result<()> {
+        ctx.accounts.cancel_offer()
+    }
+
+    pub fn accept_offer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AcceptOffer<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.accept_offer(ctx.remaining_accounts)
+    }
+
+    pub fn make_collection_offer(
+        ctx: Context<MakeCollectionOffer>,
+        id: u64,
+        filter: OfferFilter,
+        price: u64,
+        quantity: u32,
+    ) -> Result<()> {
+        ctx.accounts
+            .make_collection_offer(id, filter, price, quantity, &ctx.bumps)
+    }
+
+    pub fn cancel_collection_offer(ctx: Context<CancelCollectionOffer>) -> Result<()> {
+        ctx.accounts.cancel_collection_offer()
+    }
+
+    pub fn fill_collection_offer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FillCollectionOffer<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.fill_collection_offer(ctx.remaining_accounts)
+    }
+
+    pub fn expire_listing(ctx: Context<ExpireListing>) -> Result<()> {
+        ctx.accounts.expire_listing()
+    }
+
+    pub fn update_listing_price(ctx: Context<UpdateListingPrice>, price: u64) -> Result<()> {
+        ctx.accounts.update_listing_price(price)
+    }
+
+    pub fn update_accessory_metadata(
+        ctx: Context<UpdateAccessoryMetadata>,
+        name: String,
+        uri: String,
+    ) -> Result<()> {
+        ctx.accounts.update_accessory_metadata(name, uri)
+    }
+
+    pub fn create_drop(ctx: Context<CreateDrop>, kind: DropKind, params: DropParams) -> Result<()> {
+        ctx.accounts.create_drop(kind, params, &ctx.bumps)
+    }
+
+    pub fn set_drop_allowlist(
+        ctx: Context<SetDropAllowlist>,
+        allowlist_root: Option<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts.set_drop_allowlist(allowlist_root)
+    }
+
+    pub fn close_drop(ctx: Context<CloseDrop>) -> Result<()> {
+        ctx.accounts.close_drop()
+    }
+
+    pub fn migrate_config(ctx: Context<MigrateConfig>, _seed: u64) -> Result<()> {
+        ctx.accounts.migrate_config()
+    }
+
+    pub fn migrate_listing(ctx: Context<MigrateListing>) -> Result<()> {
+        ctx.accounts.migrate_listing()
+    }
+
+    pub fn backfill_accessory_record(
+        ctx: Context<BackfillAccessoryRecord>,
+        royalty_bps: u16,
+        slot: u8,
+    ) -> Result<()> {
+        ctx.accounts
+            .backfill_accessory_record(royalty_bps, slot, &ctx.bumps)
+    }
+
+    pub fn set_metadata_updates(ctx: Context<UpdateConfig>, enabled: bool) -> Result<()> {
+        ctx.accounts.set_metadata_updates(enabled)
+    }
+
+    pub fn set_accessory_mint_cap(ctx: Context<UpdateConfig>, cap: u32, period: i64) -> Result<()> {
+        ctx.accounts.set_accessory_mint_cap(cap, period)
+    }
+
+    pub fn set_payment_mints(ctx: Context<UpdateConfig>, payment_mints: Vec<Pubkey>) -> Result<()> {
+        ctx.accounts.set_payment_mints(payment_mints)
+    }
+
+    pub fn set_referrers(ctx: Context<UpdateConfig>, referrers: Vec<Pubkey>) -> Result<()> {
+        ctx.accounts.set_referrers(referrers)
+    }
+
+    pub fn set_swap_fee(ctx: Context<UpdateConfig>, swap_fee: u64) -> Result<()> {
+        ctx.accounts.set_swap_fee(swap_fee)
+    }
+
+    pub fn set_referral_bps(ctx: Context<UpdateConfig>, referral_bps: u16) -> Result<()> {
+        ctx.accounts.set_referral_bps(referral_bps)
+    }
+
+    pub fn set_avatar_policy(
+        ctx: Context<UpdateConfig>,
+        avatar_policy: AvatarPolicy,
+    ) -> Result<()> {
+        ctx.accounts.set_avatar_policy(avatar_policy)
+    }
+
+    pub fn set_artist_mint_cap(ctx: Context<SetArtistMintCap>, cap: Option<u32>) -> Result<()> {
+        ctx.accounts.set_artist_mint_cap(cap, &ctx.bumps)
     }
 }
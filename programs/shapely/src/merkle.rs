@@ -0,0 +1,23 @@
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+
+/// Domain separators so that a leaf can never be mistaken for an inner node
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+pub fn leaf_hash(address: &Pubkey) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, address.as_ref()]).to_bytes()
+}
+
+/// Hashes two sibling nodes in sorted order, so proofs don't need to carry positions
+pub fn node_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, left.as_ref(), right.as_ref()]).to_bytes()
+}
+
+pub fn verify_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(leaf, |node, sibling| node_hash(&node, sibling));
+
+    computed == *root
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ShapelyError;
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Returns `bps` basis points of `amount`, rounded down
+pub fn bps_of(amount: u64, bps: u16) -> Result<u64> {
+    let value = (amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(ShapelyError::MathOverflow)?
+        / BPS_DENOMINATOR as u128;
+
+    u64::try_from(value).map_err(|_| error!(ShapelyError::MathOverflow))
+}
+
+/// Moves lamports out of an account owned by this program, e.g. an escrow PDA
+pub fn transfer_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    let from_balance = from
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ShapelyError::MathOverflow)?;
+    let to_balance = to
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ShapelyError::MathOverflow)?;
+
+    **from.try_borrow_mut_lamports()? = from_balance;
+    **to.try_borrow_mut_lamports()? = to_balance;
+
+    Ok(())
+}
+
+/// How the price of a sale is divided between the treasury, the creator and the seller
+pub struct SaleSplit {
+    pub fee: u64,
+    /// Part of the treasury fee paid out to the frontend that referred the buyer
+    pub referral: u64,
+    pub royalty: u64,
+    pub seller: u64,
+}
+
+impl SaleSplit {
+    pub fn new(price: u64, fee_bps: u16, royalty_bps: u16) -> Result<Self> {
+        let fee = bps_of(price, fee_bps)?;
+        let royalty = bps_of(price, royalty_bps)?;
+        let seller = price
+            .checked_sub(fee)
+            .and_then(|rest| rest.checked_sub(royalty))
+            .ok_or(ShapelyError::MathOverflow)?;
+
+        Ok(Self {
+            fee,
+            referral: 0,
+            royalty,
+            seller,
+        })
+    }
+
+    /// Carves `referral_bps` of the treasury fee out for a referrer
+    pub fn with_referral(self, referral_bps: u16) -> Result<Self> {
+        let referral = bps_of(self.fee, referral_bps)?;
+
+        Ok(Self {
+            fee: self.fee - referral,
+            referral,
+            ..self
+        })
+    }
+}
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ShapelyError,
+    payment::{bps_of, SaleSplit},
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct CreatorShare {
+    pub address: Pubkey,
+    /// Percentage of the creators' payouts this creator receives
+    pub share: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct AccessoryRecord {
+    pub bump: u8,
+    pub accessory_mint: Pubkey,
+    /// Artist who minted the accessory
+    pub artist: Pubkey,
+    /// Avatar slot the accessory is worn in (e.g. head or eyes), as numbered by the frontend
+    pub slot: u8,
+    /// Royalty paid to the creators on resales (in basis point e.g 500 = 5%)
+    pub royalty_bps: u16,
+    /// Set on the first sale, after which the metadata can no longer be updated
+    pub metadata_locked: bool,
+    /// Everyone who made the accessory, splitting primary sale proceeds and royalties
+    #[max_len(4)]
+    pub creators: Vec<CreatorShare>,
+}
+
+impl AccessoryRecord {
+    pub const SPACE: usize = 8 + AccessoryRecord::INIT_SPACE;
+    pub const MAX_CREATORS: usize = 4;
+
+    /// No royalty is owed when the creator is the one selling
+    pub fn royalty_bps_for(&self, seller: &Pubkey) -> u16 {
+        if *seller == self.artist {
+            0
+        } else {
+            self.royalty_bps
+        }
+    }
+
+    pub fn validate_creators(creators: &[CreatorShare]) -> Result<()> {
+        require!(
+            !creators.is_empty() && creators.len() <= Self::MAX_CREATORS,
+            ShapelyError::InvalidCreatorShares
+        );
+
+        let mut total: u16 = 0;
+
+        for (index, creator) in creators.iter().enumerate() {
+            require!(
+                creator.share > 0
+                    && !creators[..index]
+                        .iter()
+                        .any(|other| other.address == creator.address),
+                ShapelyError::InvalidCreatorShares
+            );
+
+            total += creator.share as u16;
+        }
+
+        require!(total == 100, ShapelyError::InvalidCreatorShares);
+
+        Ok(())
+    }
+
+    /// Splits what the creators are owed from a sale by their shares: everything left after fees
+    /// on the primary sale, the royalty on resales. Rounding dust goes to the first creator.
+    /// Returns the amount for each creator along with what is paid to the seller, which on the
+    /// primary sale is the artist's own share.
+    pub fn creator_payouts(&self, seller: &Pubkey, split: &SaleSplit) -> Result<(Vec<u64>, u64)> {
+        let primary_sale = *seller == self.artist;
+        let pool = if primary_sale {
+            split.seller
+        } else {
+            split.royalty
+        };
+
+        let mut amounts = self
+            .creators
+            .iter()
+            .map(|creator| bps_of(pool, creator.share as u16 * 100))
+            .collect::<Result<Vec<u64>>>()?;
+
+        let distributed = amounts.iter().sum::<u64>();
+        if let Some(first) = amounts.first_mut() {
+            *first += pool - distributed;
+        }
+
+        if !primary_sale {
+            return Ok((amounts, split.seller));
+        }
+
+        let mut seller_amount = 0;
+        for (creator, amount) in self.creators.iter().zip(amounts.iter_mut()) {
+            if creator.address == *seller {
+                seller_amount += *amount;
+                *amount = 0;
+            }
+        }
+
+        Ok((amounts, seller_amount))
+    }
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct AllowlistClaim {
+    pub bump: u8,
+    pub config: Pubkey,
+    /// Avatar drop the claim was made in
+    pub drop_id: u64,
+    pub wallet: Pubkey,
+    /// Unix timestamp of the allowlisted mint
+    pub claimed_at: i64,
+}
+
+impl AllowlistClaim {
+    pub const SPACE: usize = 8 + AllowlistClaim::INIT_SPACE;
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Auction {
+    pub bump: u8,
+    pub seller: Pubkey,
+    pub accessory_mint: Pubkey,
+    /// Lowest first bid the seller accepts (in lamports)
+    pub reserve_price: u64,
+    /// Amount every new bid must add on top of the highest bid (in lamports)
+    pub min_increment: u64,
+    /// Unix timestamp at which bidding closes
+    pub end_time: i64,
+    pub highest_bidder: Option<Pubkey>,
+    /// Highest bid, escrowed in the auction account on top of its rent
+    pub highest_bid: u64,
+}
+
+impl Auction {
+    pub const SPACE: usize = 8 + Auction::INIT_SPACE;
+
+    pub fn min_bid(&self) -> Option<u64> {
+        match self.highest_bidder {
+            Some(_) => self.highest_bid.checked_add(self.min_increment),
+            None => Some(self.reserve_price),
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct BundleListing {
+    pub bump: u8,
+    pub seller: Pubkey,
+    /// Lets a seller keep several bundles listed at once
+    pub id: u64,
+    pub price: u64,
+    /// Accessories escrowed in the bundle, in the order their accounts are passed
+    #[max_len(5)]
+    pub accessory_mints: Vec<Pubkey>,
+}
+
+impl BundleListing {
+    pub const SPACE: usize = 8 + BundleListing::INIT_SPACE;
+    /// Every accessory costs a transfer, a vault and an ATA creation, so bundles stay
+    /// small enough to settle within the compute budget of a single transaction
+    pub const MAX_ACCESSORIES: usize = 5;
+}
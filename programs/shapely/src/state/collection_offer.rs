@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+
+use crate::state::AccessoryRecord;
+
+/// Which accessories of the collection can fill a collection offer
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum OfferFilter {
+    /// Any accessory in the accessory collection
+    Collection,
+    /// Any accessory minted by the given artist
+    Artist { artist: Pubkey },
+    /// Any accessory worn in the given avatar slot
+    Slot { slot: u8 },
+}
+
+impl OfferFilter {
+    pub fn matches(&self, accessory_record: &AccessoryRecord) -> bool {
+        match self {
+            OfferFilter::Collection => true,
+            OfferFilter::Artist { artist } => accessory_record.artist == *artist,
+            OfferFilter::Slot { slot } => accessory_record.slot == *slot,
+        }
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct CollectionOffer {
+    pub bump: u8,
+    pub buyer: Pubkey,
+    /// Lets a buyer keep several collection offers open at once
+    pub id: u64,
+    pub filter: OfferFilter,
+    /// Lamports paid for each accessory
+    pub price: u64,
+    /// Accessories left to buy, the escrow holds `price * remaining` lamports
+    pub remaining: u32,
+}
+
+impl CollectionOffer {
+    pub const SPACE: usize = 8 + CollectionOffer::INIT_SPACE;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accessory_record(artist: Pubkey, slot: u8) -> AccessoryRecord {
+        AccessoryRecord {
+            bump: 0,
+            accessory_mint: Pubkey::new_unique(),
+            artist,
+            slot,
+            royalty_bps: 0,
+            metadata_locked: false,
+            creators: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn collection_filter_matches_any_accessory() {
+        assert!(OfferFilter::Collection.matches(&accessory_record(Pubkey::new_unique(), 0)));
+    }
+
+    #[test]
+    fn artist_filter_matches_the_artist_only() {
+        let artist = Pubkey::new_unique();
+        let filter = OfferFilter::Artist { artist };
+
+        assert!(filter.matches(&accessory_record(artist, 0)));
+        assert!(!filter.matches(&accessory_record(Pubkey::new_unique(), 0)));
+    }
+
+    #[test]
+    fn slot_filter_matches_the_slot_only() {
+        let artist = Pubkey::new_unique();
+        let filter = OfferFilter::Slot { slot: 2 };
+
+        assert!(filter.matches(&accessory_record(artist, 2)));
+        assert!(!filter.matches(&accessory_record(artist, 3)));
+    }
+}
@@ -1,4 +1,27 @@
 use anchor_lang::prelude::*;
+use anchor_spl::metadata::MetadataAccount;
+
+use crate::{error::ShapelyError, state::DropKind};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum AvatarPolicy {
+    /// Every purchase needs the buyer to hold an avatar
+    Required,
+    /// Anyone can buy
+    NotRequired,
+    /// Only purchases straight from the artist need the buyer to hold an avatar
+    PrimaryOnly,
+}
+
+impl AvatarPolicy {
+    pub fn requires_avatar(&self, primary_sale: bool) -> bool {
+        match self {
+            AvatarPolicy::Required => true,
+            AvatarPolicy::NotRequired => false,
+            AvatarPolicy::PrimaryOnly => primary_sale,
+        }
+    }
+}
 
 #[account]
 #[derive(InitSpace)]
@@ -13,8 +36,140 @@ pub struct Config {
     pub fee: u16,
     /// Config ID
     pub seed: u64,
+    /// Authority allowed to change the config settings
+    pub admin: Pubkey,
+    /// Whether artists can update the metadata of their accessories
+    pub metadata_updates_enabled: bool,
+    /// Whether avatar mints are restricted to the avatar drop window
+    pub avatar_drop_active: bool,
+    /// Whether accessory mints are restricted to the accessory drop window
+    pub accessory_drop_active: bool,
+    /// Accessories an artist can mint per period, 0 means unlimited
+    pub accessory_mint_cap: u32,
+    /// Length of an accessory mint cap period in seconds, 0 means the cap never resets
+    pub mint_cap_period: i64,
+    /// Flat fee the taker of a swap pays to the treasury (in lamports)
+    pub swap_fee: u64,
+    /// Share of the treasury fee paid to a referring frontend on purchases (in basis points of the fee)
+    pub referral_bps: u16,
+    /// Which purchases need the buyer to hold an avatar
+    pub avatar_policy: AvatarPolicy,
+    /// SPL tokens accessories can be listed in besides SOL
+    #[max_len(5)]
+    pub payment_mints: Vec<Pubkey>,
+    /// Incremented for every avatar drop, scopes allowlist claims to the current drop
+    pub avatar_drop_id: u64,
+    /// Frontends allowed to take a referral share of the treasury fee
+    #[max_len(10)]
+    pub referrers: Vec<Pubkey>,
+}
+
+/// Layout of the config accounts created before the settings above were added
+#[derive(AnchorDeserialize)]
+pub struct LegacyConfig {
+    pub bump: u8,
+    pub treasury_bump: u8,
+    pub avatar_collection_bump: u8,
+    pub accessory_collection_bump: u8,
+    pub avatar_collection: Pubkey,
+    pub accessory_collection: Pubkey,
+    pub fee: u16,
+    pub seed: u64,
+}
+
+impl LegacyConfig {
+    pub const SPACE: usize = 8 + 4 + 32 * 2 + 2 + 8;
 }
 
 impl Config {
     pub const SPACE: usize = 8 + Config::INIT_SPACE;
+    pub const MAX_PAYMENT_MINTS: usize = 5;
+    pub const MAX_REFERRERS: usize = 10;
+
+    pub fn set_drop_active(&mut self, kind: DropKind, active: bool) {
+        match kind {
+            DropKind::Avatar => self.avatar_drop_active = active,
+            DropKind::Accessory => self.accessory_drop_active = active,
+        }
+    }
+
+    /// Checks that a metadata account belongs to `mint` and is verified in the accessory collection
+    pub fn verify_accessory(&self, metadata: &MetadataAccount, mint: &Pubkey) -> Result<()> {
+        require_keys_eq!(metadata.mint, *mint, ShapelyError::MetadataMintMismatch);
+
+        let collection = metadata
+            .collection
+            .as_ref()
+            .ok_or(ShapelyError::AccessoryMissingCollection)?;
+
+        require_keys_eq!(
+            collection.key,
+            self.accessory_collection,
+            ShapelyError::AccessoryWrongCollection
+        );
+        require!(collection.verified, ShapelyError::AccessoryNotVerified);
+
+        Ok(())
+    }
+
+    /// Checks that a metadata account belongs to `mint` and is verified in the avatar collection
+    pub fn verify_avatar(&self, metadata: &MetadataAccount, mint: &Pubkey) -> Result<()> {
+        require_keys_eq!(metadata.mint, *mint, ShapelyError::MetadataMintMismatch);
+
+        let collection = metadata
+            .collection
+            .as_ref()
+            .ok_or(ShapelyError::AvatarMissingCollection)?;
+
+        require_keys_eq!(
+            collection.key,
+            self.avatar_collection,
+            ShapelyError::AvatarWrongCollection
+        );
+        require!(collection.verified, ShapelyError::AvatarNotVerified);
+
+        Ok(())
+    }
+
+    /// Checks the buyer's avatar, which the avatar policy may let them leave out
+    pub fn verify_buyer_avatar(
+        &self,
+        avatar: Option<(&MetadataAccount, &Pubkey)>,
+        primary_sale: bool,
+    ) -> Result<()> {
+        match avatar {
+            Some((metadata, mint)) => self.verify_avatar(metadata, mint),
+            None => {
+                require!(
+                    !self.avatar_policy.requires_avatar(primary_sale),
+                    ShapelyError::AvatarRequired
+                );
+
+                Ok(())
+            }
+        }
+    }
+
+    /// Checks the terms a seller picked when listing an accessory
+    pub fn validate_listing_terms(
+        &self,
+        payment_mint: Option<Pubkey>,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        if let Some(expires_at) = expires_at {
+            require!(
+                expires_at > Clock::get()?.unix_timestamp,
+                ShapelyError::InvalidListingExpiry
+            );
+        }
+
+        if let Some(payment_mint) = payment_mint {
+            require!(
+                self.payment_mints.contains(&payment_mint),
+                ShapelyError::PaymentMintNotAccepted
+            );
+        }
+
+        Ok(())
+    }
 }
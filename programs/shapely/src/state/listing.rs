@@ -1,13 +1,196 @@
 use anchor_lang::prelude::*;
 
+use crate::error::ShapelyError;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum DecayCurve {
+    /// Price decreases continuously between the start and end time
+    Linear,
+    /// Price decreases once every `step_duration` seconds
+    Stepwise { step_duration: i64 },
+}
+
+/// Declining price schedule of a dutch auction listing
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct DutchAuction {
+    pub start_price: u64,
+    pub end_price: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub curve: DecayCurve,
+}
+
+impl DutchAuction {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.start_price >= self.end_price && self.start_time < self.end_time,
+            ShapelyError::InvalidDutchAuction
+        );
+
+        if let DecayCurve::Stepwise { step_duration } = self.curve {
+            require!(step_duration > 0, ShapelyError::InvalidDutchAuction);
+        }
+
+        Ok(())
+    }
+
+    pub fn price_at(&self, now: i64) -> Result<u64> {
+        if now <= self.start_time {
+            return Ok(self.start_price);
+        }
+        if now >= self.end_time {
+            return Ok(self.end_price);
+        }
+
+        let elapsed = now
+            .checked_sub(self.start_time)
+            .ok_or(ShapelyError::MathOverflow)?;
+        let elapsed = match self.curve {
+            DecayCurve::Linear => elapsed,
+            DecayCurve::Stepwise { step_duration } => elapsed - elapsed % step_duration,
+        };
+        let duration = self
+            .end_time
+            .checked_sub(self.start_time)
+            .ok_or(ShapelyError::MathOverflow)?;
+
+        let decay =
+            (self.start_price - self.end_price) as u128 * elapsed as u128 / duration as u128;
+
+        u64::try_from(self.start_price as u128 - decay)
+            .map_err(|_| error!(ShapelyError::MathOverflow))
+    }
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct Listing {
     pub bump: u8,
     pub price: u64,
     pub accessory_mint: Pubkey,
+    /// SPL token the price is denominated in, lamports if not set
+    pub payment_mint: Option<Pubkey>,
+    /// Unix timestamp after which the listing can no longer be bought
+    pub expires_at: Option<i64>,
+    /// Price schedule when the listing is a dutch auction, `price` is fixed otherwise
+    pub dutch_auction: Option<DutchAuction>,
+    /// Whether the accessory stays frozen in the seller's wallet instead of a listing vault
+    pub delegated: bool,
+    /// Whether the seller's share is credited to their proceeds account instead of paid out
+    pub claimable_proceeds: bool,
+    /// Only wallet allowed to buy the listing, anyone can when not set
+    pub reserved_buyer: Option<Pubkey>,
+}
+
+/// Layout of the listing accounts created before the listing terms above were added
+#[derive(AnchorDeserialize)]
+pub struct LegacyListing {
+    pub bump: u8,
+    pub price: u64,
+    pub accessory_mint: Pubkey,
+}
+
+impl LegacyListing {
+    pub const SPACE: usize = 8 + 1 + 8 + 32;
 }
 
 impl Listing {
     pub const SPACE: usize = 8 + Listing::INIT_SPACE;
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+
+    pub fn can_be_bought_by(&self, buyer: &Pubkey) -> bool {
+        self.reserved_buyer
+            .is_none_or(|reserved_buyer| reserved_buyer == *buyer)
+    }
+
+    pub fn price_at(&self, now: i64) -> Result<u64> {
+        match self.dutch_auction {
+            Some(dutch_auction) => dutch_auction.price_at(now),
+            None => Ok(self.price),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dutch_auction(curve: DecayCurve) -> DutchAuction {
+        DutchAuction {
+            start_price: 1_000,
+            end_price: 200,
+            start_time: 100,
+            end_time: 500,
+            curve,
+        }
+    }
+
+    #[test]
+    fn linear_price_decays_with_time() {
+        let dutch_auction = dutch_auction(DecayCurve::Linear);
+
+        assert_eq!(dutch_auction.price_at(100).unwrap(), 1_000);
+        assert_eq!(dutch_auction.price_at(101).unwrap(), 998);
+        assert_eq!(dutch_auction.price_at(300).unwrap(), 600);
+        assert_eq!(dutch_auction.price_at(499).unwrap(), 202);
+        assert_eq!(dutch_auction.price_at(500).unwrap(), 200);
+    }
+
+    #[test]
+    fn stepwise_price_decays_once_per_step() {
+        let dutch_auction = dutch_auction(DecayCurve::Stepwise { step_duration: 100 });
+
+        assert_eq!(dutch_auction.price_at(199).unwrap(), 1_000);
+        assert_eq!(dutch_auction.price_at(200).unwrap(), 800);
+        assert_eq!(dutch_auction.price_at(350).unwrap(), 600);
+        assert_eq!(dutch_auction.price_at(499).unwrap(), 400);
+        assert_eq!(dutch_auction.price_at(500).unwrap(), 200);
+    }
+
+    #[test]
+    fn price_is_clamped_outside_the_auction() {
+        let dutch_auction = dutch_auction(DecayCurve::Linear);
+
+        assert_eq!(dutch_auction.price_at(i64::MIN).unwrap(), 1_000);
+        assert_eq!(dutch_auction.price_at(0).unwrap(), 1_000);
+        assert_eq!(dutch_auction.price_at(10_000).unwrap(), 200);
+        assert_eq!(dutch_auction.price_at(i64::MAX).unwrap(), 200);
+    }
+
+    #[test]
+    fn extreme_times_return_an_error() {
+        let dutch_auction = DutchAuction {
+            start_time: i64::MIN,
+            end_time: i64::MAX,
+            ..dutch_auction(DecayCurve::Linear)
+        };
+
+        dutch_auction.validate().unwrap();
+        assert_eq!(
+            dutch_auction.price_at(0).unwrap_err(),
+            ShapelyError::MathOverflow.into()
+        );
+    }
+
+    #[test]
+    fn legacy_listing_reads_the_original_layout() {
+        let accessory_mint = Pubkey::new_unique();
+
+        let mut data = Listing::DISCRIMINATOR.to_vec();
+        data.push(254);
+        data.extend_from_slice(&1_500u64.to_le_bytes());
+        data.extend_from_slice(accessory_mint.as_ref());
+
+        assert_eq!(data.len(), LegacyListing::SPACE);
+        assert!(data.len() < Listing::SPACE);
+
+        let legacy = LegacyListing::deserialize(&mut &data[8..]).unwrap();
+
+        assert_eq!(legacy.bump, 254);
+        assert_eq!(legacy.price, 1_500);
+        assert_eq!(legacy.accessory_mint, accessory_mint);
+    }
 }
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ShapelyError;
+
+#[account]
+#[derive(InitSpace)]
+pub struct MintCounter {
+    pub bump: u8,
+    pub config: Pubkey,
+    pub artist: Pubkey,
+    /// Unix timestamp at which the current mint period started
+    pub period_start: i64,
+    /// Accessories minted by the artist in the current period
+    pub minted: u32,
+    /// Cap granted by the admin, replaces the config cap when set
+    pub cap_override: Option<u32>,
+}
+
+impl MintCounter {
+    pub const SPACE: usize = 8 + MintCounter::INIT_SPACE;
+
+    pub fn is_initialized(&self) -> bool {
+        self.artist != Pubkey::default()
+    }
+
+    /// Counts a mint against the cap, starting a new period once the current one is over.
+    /// A cap of 0 means unlimited and a period of 0 means the cap applies for all time.
+    pub fn record_mint(&mut self, config_cap: u32, period: i64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        if period > 0 && now.saturating_sub(self.period_start) >= period {
+            self.period_start = now;
+            self.minted = 0;
+        }
+
+        let cap = self.cap_override.unwrap_or(config_cap);
+        if cap > 0 {
+            require!(self.minted < cap, ShapelyError::MintCapReached);
+        }
+
+        self.minted = self
+            .minted
+            .checked_add(1)
+            .ok_or(ShapelyError::MathOverflow)?;
+
+        Ok(())
+    }
+}
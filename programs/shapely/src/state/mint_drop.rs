@@ -0,0 +1,187 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ShapelyError, merkle};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum DropKind {
+    Avatar,
+    Accessory,
+}
+
+impl DropKind {
+    pub fn seed(&self) -> &'static [u8] {
+        match self {
+            DropKind::Avatar => b"avatar drop",
+            DropKind::Accessory => b"accessory drop",
+        }
+    }
+}
+
+/// Unit the mint window of a drop is expressed in
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum DropClock {
+    UnixTimestamp,
+    Slot,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct MintDrop {
+    pub bump: u8,
+    pub kind: DropKind,
+    pub clock: DropClock,
+    /// First unix timestamp (or slot) at which minting is open
+    pub start: u64,
+    /// Unix timestamp (or slot) at which minting closes
+    pub end: u64,
+    /// Maximum number of mints in the drop, unlimited if not set
+    pub supply_cap: Option<u64>,
+    pub minted: u64,
+    /// Every uri minted in the drop must start with this prefix
+    #[max_len(100)]
+    pub uri_prefix: String,
+    /// Merkle root of the allowlisted wallets, anyone can mint if not set
+    pub allowlist_root: Option<[u8; 32]>,
+}
+
+impl MintDrop {
+    pub const SPACE: usize = 8 + MintDrop::INIT_SPACE;
+    pub const MAX_URI_PREFIX_LEN: usize = 100;
+
+    pub fn verify_allowlist(&self, wallet: &Pubkey, proof: &[[u8; 32]]) -> Result<()> {
+        if let Some(root) = self.allowlist_root.as_ref() {
+            require!(
+                merkle::verify_proof(proof, root, merkle::leaf_hash(wallet)),
+                ShapelyError::NotAllowlisted
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn record_mint(&mut self, uri: &str, clock: &Clock) -> Result<()> {
+        let now = match self.clock {
+            DropClock::UnixTimestamp => u64::try_from(clock.unix_timestamp).unwrap_or_default(),
+            DropClock::Slot => clock.slot,
+        };
+
+        require!(now >= self.start, ShapelyError::DropNotStarted);
+        require!(now < self.end, ShapelyError::DropEnded);
+
+        if let Some(supply_cap) = self.supply_cap {
+            require!(self.minted < supply_cap, ShapelyError::DropSoldOut);
+        }
+
+        require!(
+            uri.starts_with(self.uri_prefix.as_str()),
+            ShapelyError::InvalidDropUri
+        );
+
+        self.minted = self
+            .minted
+            .checked_add(1)
+            .ok_or(ShapelyError::MathOverflow)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URI: &str = "https://shapely.xyz/accessories/1.json";
+
+    fn mint_drop(clock: DropClock, supply_cap: Option<u64>) -> MintDrop {
+        MintDrop {
+            bump: 0,
+            kind: DropKind::Accessory,
+            clock,
+            start: 100,
+            end: 200,
+            supply_cap,
+            minted: 0,
+            uri_prefix: "https://shapely.xyz/".to_string(),
+            allowlist_root: None,
+        }
+    }
+
+    fn clock_at(unix_timestamp: i64, slot: u64) -> Clock {
+        Clock {
+            slot,
+            unix_timestamp,
+            ..Clock::default()
+        }
+    }
+
+    #[test]
+    fn records_mints_inside_the_window() {
+        let mut mint_drop = mint_drop(DropClock::UnixTimestamp, None);
+
+        mint_drop.record_mint(URI, &clock_at(100, 0)).unwrap();
+        mint_drop.record_mint(URI, &clock_at(199, 0)).unwrap();
+
+        assert_eq!(mint_drop.minted, 2);
+    }
+
+    #[test]
+    fn rejects_mints_before_the_start() {
+        let mut mint_drop = mint_drop(DropClock::UnixTimestamp, None);
+
+        assert_eq!(
+            mint_drop.record_mint(URI, &clock_at(99, 0)).unwrap_err(),
+            ShapelyError::DropNotStarted.into()
+        );
+    }
+
+    #[test]
+    fn rejects_mints_from_the_end() {
+        let mut mint_drop = mint_drop(DropClock::UnixTimestamp, None);
+
+        assert_eq!(
+            mint_drop.record_mint(URI, &clock_at(200, 0)).unwrap_err(),
+            ShapelyError::DropEnded.into()
+        );
+    }
+
+    #[test]
+    fn uses_slots_for_slot_drops() {
+        let mut mint_drop = mint_drop(DropClock::Slot, None);
+
+        mint_drop.record_mint(URI, &clock_at(0, 150)).unwrap();
+        assert_eq!(
+            mint_drop.record_mint(URI, &clock_at(150, 99)).unwrap_err(),
+            ShapelyError::DropNotStarted.into()
+        );
+        assert_eq!(
+            mint_drop.record_mint(URI, &clock_at(150, 200)).unwrap_err(),
+            ShapelyError::DropEnded.into()
+        );
+    }
+
+    #[test]
+    fn rejects_mints_past_the_supply_cap() {
+        let mut mint_drop = mint_drop(DropClock::UnixTimestamp, Some(1));
+
+        mint_drop.record_mint(URI, &clock_at(150, 0)).unwrap();
+
+        assert_eq!(
+            mint_drop.record_mint(URI, &clock_at(150, 0)).unwrap_err(),
+            ShapelyError::DropSoldOut.into()
+        );
+        assert_eq!(mint_drop.minted, 1);
+    }
+
+    #[test]
+    fn rejects_uris_outside_the_prefix() {
+        let mut mint_drop = mint_drop(DropClock::UnixTimestamp, None);
+
+        assert_eq!(
+            mint_drop
+                .record_mint("https://example.com/1.json", &clock_at(150, 0))
+                .unwrap_err(),
+            ShapelyError::InvalidDropUri.into()
+        );
+        assert_eq!(mint_drop.minted, 0);
+    }
+}
@@ -1,5 +1,27 @@
+pub mod accessory_record;
+pub mod allowlist_claim;
+pub mod auction;
+pub mod bundle_listing;
+pub mod collection_offer;
 pub mod config;
 pub mod listing;
+pub mod mint_counter;
+pub mod mint_drop;
+pub mod offer;
+pub mod proceeds;
+pub mod rental;
+pub mod swap_offer;
 
+pub use accessory_record::*;
+pub use allowlist_claim::*;
+pub use auction::*;
+pub use bundle_listing::*;
+pub use collection_offer::*;
 pub use config::*;
 pub use listing::*;
+pub use mint_counter::*;
+pub use mint_drop::*;
+pub use offer::*;
+pub use proceeds::*;
+pub use rental::*;
+pub use swap_offer::*;
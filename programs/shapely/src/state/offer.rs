@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Offer {
+    pub bump: u8,
+    pub buyer: Pubkey,
+    pub accessory_mint: Pubkey,
+    /// Lamports escrowed in the offer account on top of its rent
+    pub amount: u64,
+}
+
+impl Offer {
+    pub const SPACE: usize = 8 + Offer::INIT_SPACE;
+}
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ShapelyError;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Proceeds {
+    pub bump: u8,
+    pub seller: Pubkey,
+    /// Lamports credited from sales and not claimed yet, held on top of the account rent
+    pub claimable: u64,
+    /// Lamports ever credited to the seller
+    pub total_credited: u64,
+    /// Lamports ever claimed by the seller
+    pub total_claimed: u64,
+    /// Listing rent refunded into the claimable balance, kept apart from the sale totals
+    pub total_rent_refunded: u64,
+}
+
+impl Proceeds {
+    pub const SPACE: usize = 8 + Proceeds::INIT_SPACE;
+
+    pub fn is_initialized(&self) -> bool {
+        self.seller != Pubkey::default()
+    }
+
+    /// Sets up a fresh proceeds account for the seller, leaving an existing one untouched
+    pub fn open(&mut self, seller: Pubkey, payment_mint: Option<Pubkey>, bump: u8) -> Result<()> {
+        require!(
+            payment_mint.is_none(),
+            ShapelyError::ClaimableProceedsSolOnly
+        );
+
+        if !self.is_initialized() {
+            self.bump = bump;
+            self.seller = seller;
+            self.claimable = 0;
+            self.total_credited = 0;
+            self.total_claimed = 0;
+            self.total_rent_refunded = 0;
+        }
+
+        Ok(())
+    }
+
+    pub fn credit(&mut self, amount: u64) -> Result<()> {
+        self.claimable = self
+            .claimable
+            .checked_add(amount)
+            .ok_or(ShapelyError::MathOverflow)?;
+        self.total_credited = self
+            .total_credited
+            .checked_add(amount)
+            .ok_or(ShapelyError::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// Adds refunded rent to the claimable balance without counting it as a sale
+    pub fn refund_rent(&mut self, amount: u64) -> Result<()> {
+        self.claimable = self
+            .claimable
+            .checked_add(amount)
+            .ok_or(ShapelyError::MathOverflow)?;
+        self.total_rent_refunded = self
+            .total_rent_refunded
+            .checked_add(amount)
+            .ok_or(ShapelyError::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// Empties the claimable balance and returns the amount to pay out
+    pub fn claim(&mut self) -> Result<u64> {
+        let amount = self.claimable;
+
+        self.claimable = 0;
+        self.total_claimed = self
+            .total_claimed
+            .checked_add(amount)
+            .ok_or(ShapelyError::MathOverflow)?;
+
+        Ok(amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refunded_rent_is_claimable_but_not_counted_as_sales() {
+        let mut proceeds = Proceeds {
+            bump: 255,
+            seller: Pubkey::new_unique(),
+            claimable: 0,
+            total_credited: 0,
+            total_claimed: 0,
+            total_rent_refunded: 0,
+        };
+
+        proceeds.credit(1_000).unwrap();
+        proceeds.refund_rent(300).unwrap();
+
+        assert_eq!(proceeds.claimable, 1_300);
+        assert_eq!(proceeds.total_credited, 1_000);
+        assert_eq!(proceeds.total_rent_refunded, 300);
+
+        assert_eq!(proceeds.claim().unwrap(), 1_300);
+        assert_eq!(proceeds.claimable, 0);
+        assert_eq!(proceeds.total_claimed, 1_300);
+    }
+}
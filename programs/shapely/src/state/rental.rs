@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ShapelyError;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Rental {
+    pub bump: u8,
+    pub owner: Pubkey,
+    pub accessory_mint: Pubkey,
+    /// Price of one rental period (in lamports)
+    pub price_per_period: u64,
+    /// Length of one rental period in seconds
+    pub period: i64,
+    /// Most periods a renter can book at once
+    pub max_periods: u32,
+    pub renter: Option<Pubkey>,
+    /// Unix timestamp at which the current rental ends and the accessory can be reclaimed
+    pub rented_until: i64,
+}
+
+impl Rental {
+    pub const SPACE: usize = 8 + Rental::INIT_SPACE;
+
+    /// Returns the total price and end timestamp of renting for `periods` starting at `now`
+    pub fn quote(&self, periods: u32, now: i64) -> Result<(u64, i64)> {
+        require!(
+            periods > 0 && periods <= self.max_periods,
+            ShapelyError::InvalidRentalPeriods
+        );
+
+        let price = self
+            .price_per_period
+            .checked_mul(periods as u64)
+            .ok_or(ShapelyError::MathOverflow)?;
+        let rented_until = self
+            .period
+            .checked_mul(periods as i64)
+            .and_then(|duration| now.checked_add(duration))
+            .ok_or(ShapelyError::MathOverflow)?;
+
+        Ok((price, rented_until))
+    }
+}
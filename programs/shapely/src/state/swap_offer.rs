@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct SwapOffer {
+    pub bump: u8,
+    pub maker: Pubkey,
+    /// Config the swap was made under, which sets the fee charged when it is filled
+    pub config: Pubkey,
+    /// Lets a maker keep several swaps open at once
+    pub id: u64,
+    /// Accessories escrowed by the maker, in the order their accounts are passed
+    #[max_len(3)]
+    pub offered_mints: Vec<Pubkey>,
+    /// Accessories the taker has to hand over, in the order their accounts are passed
+    #[max_len(3)]
+    pub requested_mints: Vec<Pubkey>,
+    /// Lamports the taker pays the maker on top of the requested accessories
+    pub sol_top_up: u64,
+}
+
+impl SwapOffer {
+    pub const SPACE: usize = 8 + SwapOffer::INIT_SPACE;
+    /// Keeps a fill, which moves both sides of the swap, within the compute budget
+    pub const MAX_ACCESSORIES: usize = 3;
+}
@@ -8,15 +8,20 @@ import {
 	address,
 } from "gill";
 import {
+	ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
 	getAssociatedTokenAccountAddress,
 	getTokenMetadataAddress,
 	TOKEN_METADATA_PROGRAM_ADDRESS,
+	TOKEN_PROGRAM_ADDRESS,
 } from "gill/programs";
+import { createHash } from "crypto";
 import {
 	Connection,
 	Keypair,
 	LAMPORTS_PER_SOL,
 	PublicKey,
+	SystemProgram,
+	TransactionInstruction,
 } from "@solana/web3.js";
 
 const addressEncoder = getAddressEncoder();
@@ -97,6 +102,191 @@ export async function getListingPDA(
 	return new PublicKey(listingPDA);
 }
 
+export async function getBundlePDA(
+	seller: PublicKey,
+	id: number
+): Promise<PublicKey> {
+	const idBuffer = Buffer.alloc(8);
+	idBuffer.writeBigUInt64LE(BigInt(id), 0);
+
+	const [bundlePDA] = await getProgramDerivedAddress({
+		programAddress: PROGRAM_ID,
+		seeds: [
+			"bundle",
+			addressEncoder.encode(address(seller.toBase58())),
+			idBuffer,
+		],
+	});
+
+	return new PublicKey(bundlePDA);
+}
+
+export async function getSwapPDA(
+	maker: PublicKey,
+	id: number
+): Promise<PublicKey> {
+	const idBuffer = Buffer.alloc(8);
+	idBuffer.writeBigUInt64LE(BigInt(id), 0);
+
+	const [swapPDA] = await getProgramDerivedAddress({
+		programAddress: PROGRAM_ID,
+		seeds: [
+			"swap",
+			addressEncoder.encode(address(maker.toBase58())),
+			idBuffer,
+		],
+	});
+
+	return new PublicKey(swapPDA);
+}
+
+export async function getAuctionPDA(
+	accessoryMint: PublicKey,
+	seller: PublicKey
+): Promise<PublicKey> {
+	const [auctionPDA] = await getProgramDerivedAddress({
+		programAddress: PROGRAM_ID,
+		seeds: [
+			"auction",
+			addressEncoder.encode(address(accessoryMint.toBase58())),
+			addressEncoder.encode(address(seller.toBase58())),
+		],
+	});
+
+	return new PublicKey(auctionPDA);
+}
+
+export async function getRentalPDA(
+	accessoryMint: PublicKey,
+	owner: PublicKey
+): Promise<PublicKey> {
+	const [rentalPDA] = await getProgramDerivedAddress({
+		programAddress: PROGRAM_ID,
+		seeds: [
+			"rental",
+			addressEncoder.encode(address(accessoryMint.toBase58())),
+			addressEncoder.encode(address(owner.toBase58())),
+		],
+	});
+
+	return new PublicKey(rentalPDA);
+}
+
+export async function getProceedsPDA(seller: PublicKey): Promise<PublicKey> {
+	const [proceedsPDA] = await getProgramDerivedAddress({
+		programAddress: PROGRAM_ID,
+		seeds: ["proceeds", addressEncoder.encode(address(seller.toBase58()))],
+	});
+
+	return new PublicKey(proceedsPDA);
+}
+
+export async function getAllowlistClaimPDA(
+	config: PublicKey,
+	dropId: number,
+	wallet: PublicKey
+): Promise<PublicKey> {
+	const dropIdBuffer = Buffer.alloc(8);
+	dropIdBuffer.writeBigUInt64LE(BigInt(dropId), 0);
+
+	const [allowlistClaimPDA] = await getProgramDerivedAddress({
+		programAddress: PROGRAM_ID,
+		seeds: [
+			"allowlist claim",
+			addressEncoder.encode(address(config.toBase58())),
+			dropIdBuffer,
+			addressEncoder.encode(address(wallet.toBase58())),
+		],
+	});
+
+	return new PublicKey(allowlistClaimPDA);
+}
+
+// Mirrors the hashing in programs/shapely/src/merkle.rs
+export function allowlistLeaf(wallet: PublicKey): Buffer {
+	return createHash("sha256")
+		.update(Buffer.from([0]))
+		.update(wallet.toBuffer())
+		.digest();
+}
+
+export function allowlistNode(a: Buffer, b: Buffer): Buffer {
+	const [left, right] = Buffer.compare(a, b) <= 0 ? [a, b] : [b, a];
+
+	return createHash("sha256")
+		.update(Buffer.from([1]))
+		.update(left)
+		.update(right)
+		.digest();
+}
+
+export async function getOfferPDA(
+	accessoryMint: PublicKey,
+	buyer: PublicKey
+): Promise<PublicKey> {
+	const [offerPDA] = await getProgramDerivedAddress({
+		programAddress: PROGRAM_ID,
+		seeds: [
+			"offer",
+			addressEncoder.encode(address(accessoryMint.toBase58())),
+			addressEncoder.encode(address(buyer.toBase58())),
+		],
+	});
+
+	return new PublicKey(offerPDA);
+}
+
+export async function getCollectionOfferPDA(
+	config: PublicKey,
+	buyer: PublicKey,
+	id: number
+): Promise<PublicKey> {
+	const idBuffer = Buffer.alloc(8);
+	idBuffer.writeBigUInt64LE(BigInt(id), 0);
+
+	const [collectionOfferPDA] = await getProgramDerivedAddress({
+		programAddress: PROGRAM_ID,
+		seeds: [
+			"collection offer",
+			addressEncoder.encode(address(config.toBase58())),
+			addressEncoder.encode(address(buyer.toBase58())),
+			idBuffer,
+		],
+	});
+
+	return new PublicKey(collectionOfferPDA);
+}
+
+export async function getAccessoryRecordPDA(
+	accessoryMint: PublicKey
+): Promise<PublicKey> {
+	const [accessoryRecordPDA] = await getProgramDerivedAddress({
+		programAddress: PROGRAM_ID,
+		seeds: [
+			"accessory",
+			addressEncoder.encode(address(accessoryMint.toBase58())),
+		],
+	});
+
+	return new PublicKey(accessoryRecordPDA);
+}
+
+export async function getMintCounterPDA(
+	config: PublicKey,
+	artist: PublicKey
+): Promise<PublicKey> {
+	const [mintCounterPDA] = await getProgramDerivedAddress({
+		programAddress: PROGRAM_ID,
+		seeds: [
+			"mint counter",
+			addressEncoder.encode(address(config.toBase58())),
+			addressEncoder.encode(address(artist.toBase58())),
+		],
+	});
+
+	return new PublicKey(mintCounterPDA);
+}
+
 export async function getCollectionMintPDA(
 	collectionType: "avatar" | "accessory",
 	config: PublicKey
@@ -112,6 +302,21 @@ export async function getCollectionMintPDA(
 	return new PublicKey(collectionMintPDA);
 }
 
+export async function getDropPDA(
+	dropKind: "avatar" | "accessory",
+	config: PublicKey
+): Promise<PublicKey> {
+	const [dropPDA] = await getProgramDerivedAddress({
+		programAddress: PROGRAM_ID,
+		seeds: [
+			`${dropKind} drop`,
+			addressEncoder.encode(address(config.toBase58())),
+		],
+	});
+
+	return new PublicKey(dropPDA);
+}
+
 export async function getMasterEdition(mint: PublicKey): Promise<PublicKey> {
 	const [masterEdition] = await getProgramDerivedAddress({
 		programAddress: TOKEN_METADATA_PROGRAM_ADDRESS,
@@ -141,3 +346,76 @@ export async function getAvatarMintPDA(
 
 	return new PublicKey(NFTMintPDA);
 }
+
+const MINT_SIZE = 82;
+
+// SPL token instructions built by hand, the tests have no SPL token client
+export async function createPaymentMintInstructions(
+	connection: Connection,
+	payer: PublicKey,
+	mint: PublicKey,
+	decimals: number
+): Promise<TransactionInstruction[]> {
+	const tokenProgram = new PublicKey(TOKEN_PROGRAM_ADDRESS);
+
+	// InitializeMint2 with the payer as mint authority and no freeze authority
+	const data = Buffer.alloc(35);
+	data.writeUInt8(20, 0);
+	data.writeUInt8(decimals, 1);
+	payer.toBuffer().copy(data, 2);
+
+	return [
+		SystemProgram.createAccount({
+			fromPubkey: payer,
+			newAccountPubkey: mint,
+			lamports: await connection.getMinimumBalanceForRentExemption(MINT_SIZE),
+			space: MINT_SIZE,
+			programId: tokenProgram,
+		}),
+		new TransactionInstruction({
+			programId: tokenProgram,
+			keys: [{ pubkey: mint, isSigner: false, isWritable: true }],
+			data,
+		}),
+	];
+}
+
+export async function mintPaymentTokensInstructions(
+	payer: PublicKey,
+	mint: PublicKey,
+	owner: PublicKey,
+	amount: number
+): Promise<TransactionInstruction[]> {
+	const tokenProgram = new PublicKey(TOKEN_PROGRAM_ADDRESS);
+	const ata = await getATA(mint, owner);
+
+	// MintTo signed by the payer as mint authority
+	const data = Buffer.alloc(9);
+	data.writeUInt8(7, 0);
+	data.writeBigUInt64LE(BigInt(amount), 1);
+
+	return [
+		new TransactionInstruction({
+			programId: new PublicKey(ASSOCIATED_TOKEN_PROGRAM_ADDRESS),
+			keys: [
+				{ pubkey: payer, isSigner: true, isWritable: true },
+				{ pubkey: ata, isSigner: false, isWritable: true },
+				{ pubkey: owner, isSigner: false, isWritable: false },
+				{ pubkey: mint, isSigner: false, isWritable: false },
+				{ pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+				{ pubkey: tokenProgram, isSigner: false, isWritable: false },
+			],
+			// CreateIdempotent
+			data: Buffer.from([1]),
+		}),
+		new TransactionInstruction({
+			programId: tokenProgram,
+			keys: [
+				{ pubkey: mint, isSigner: false, isWritable: true },
+				{ pubkey: ata, isSigner: false, isWritable: true },
+				{ pubkey: payer, isSigner: true, isWritable: false },
+			],
+			data,
+		}),
+	];
+}
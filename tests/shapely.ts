@@ -11,23 +11,43 @@ import {
 	TOKEN_PROGRAM_ADDRESS,
 } from "gill/programs";
 import {
+	AccountMeta,
+	AddressLookupTableProgram,
 	ComputeBudgetProgram,
 	Keypair,
 	LAMPORTS_PER_SOL,
 	PublicKey,
+	SystemProgram,
 	Transaction,
+	TransactionMessage,
+	VersionedTransaction,
 } from "@solana/web3.js";
 
 import {
+	allowlistLeaf,
+	allowlistNode,
+	createPaymentMintInstructions,
 	generateAndAirdropSigner,
 	getATA,
+	getAccessoryRecordPDA,
+	getAllowlistClaimPDA,
+	getAuctionPDA,
 	getAvatarMintPDA,
+	getBundlePDA,
 	getCollectionMintPDA,
+	getCollectionOfferPDA,
 	getConfigPDA,
+	getDropPDA,
 	getListingPDA,
 	getMasterEdition,
 	getMetadataAccount,
+	getMintCounterPDA,
+	getOfferPDA,
+	getProceedsPDA,
+	getRentalPDA,
+	getSwapPDA,
 	getTreasuryPDA,
+	mintPaymentTokensInstructions,
 } from "./helpers";
 
 import payerWallet from "../payer-wallet.json";
@@ -48,6 +68,8 @@ describe("Shapely", () => {
 	let config: PublicKey;
 	let treasury: PublicKey;
 
+	let avatarDrop: PublicKey;
+	let accessoryDrop: PublicKey;
 	let avatarMint: PublicKey;
 	let avatarMetadata: PublicKey;
 	let avatarMasterEdition: PublicKey;
@@ -57,6 +79,7 @@ describe("Shapely", () => {
 	let avatarCollectionMasterEdition: PublicKey;
 
 	let accessoryMint: Keypair;
+	let accessoryRecord: PublicKey;
 	let accessoryMetadata: PublicKey;
 	let accessoryMasterEdition: PublicKey;
 	let accessoryCollection: PublicKey;
@@ -64,13 +87,31 @@ describe("Shapely", () => {
 	let accessoryCollectionMetadata: PublicKey;
 	let accessoryCollectionMasterEdition: PublicKey;
 
+	let bundle: PublicKey;
+	let bundleAccessoryMint: Keypair;
+	let bundleAccessoryRecord: PublicKey;
+	let bundleAccessoryMetadata: PublicKey;
+	let swap: PublicKey;
+	let rental: PublicKey;
+	let sellerProceeds: PublicKey;
+	let rentalVault: PublicKey;
+	let auction: PublicKey;
+	let auctionVault: PublicKey;
+	let offer: PublicKey;
+	let collectionOffer: PublicKey;
 	let listing: PublicKey;
 	let listingVault: PublicKey;
 	let artistAccessoryAta: PublicKey;
+	let artistMintCounter: PublicKey;
 	let collectorAvatarAta: PublicKey;
 	let collectorAccessoryAta: PublicKey;
 
 	const configSeed = Math.floor(Math.random() * 10_000_000_000);
+	const collectionOfferId = 1;
+	const bundleId = 1;
+	const swapId = 1;
+	const swapFee = 0.001 * LAMPORTS_PER_SOL;
+	const referralBps = 2_000; // 20% of the fee
 	const fee = 150; // 1.5%
 
 	const avatarName = "AVATAR-#001";
@@ -78,6 +119,13 @@ describe("Shapely", () => {
 
 	const accessoryName = "ACCESSORY-#001";
 	const accessoryURI = "https://www.jsonkeeper.com/b/QOVHK";
+	const royaltyBps = 500; // 5%
+	const accessorySlot = 0; // Head
+
+	// Devnet USDC
+	const usdcMint = new PublicKey(
+		"4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU"
+	);
 
 	before(async () => {
 		// For localnet
@@ -99,6 +147,8 @@ describe("Shapely", () => {
 		avatarCollectionMasterEdition =
 			await getMasterEdition(avatarCollection);
 
+		avatarDrop = await getDropPDA("avatar", config);
+		accessoryDrop = await getDropPDA("accessory", config);
 		avatarMint = await getAvatarMintPDA(
 			collector.publicKey,
 			avatarCollection
@@ -114,16 +164,44 @@ describe("Shapely", () => {
 			await getMasterEdition(accessoryCollection);
 
 		accessoryMint = Keypair.generate();
+		accessoryRecord = await getAccessoryRecordPDA(accessoryMint.publicKey);
 		accessoryMetadata = await getMetadataAccount(accessoryMint.publicKey);
 		accessoryMasterEdition = await getMasterEdition(
 			accessoryMint.publicKey
 		);
 
+		bundle = await getBundlePDA(artist.publicKey, bundleId);
+		bundleAccessoryMint = Keypair.generate();
+		bundleAccessoryRecord = await getAccessoryRecordPDA(
+			bundleAccessoryMint.publicKey
+		);
+		bundleAccessoryMetadata = await getMetadataAccount(
+			bundleAccessoryMint.publicKey
+		);
+		swap = await getSwapPDA(artist.publicKey, swapId);
+		sellerProceeds = await getProceedsPDA(artist.publicKey);
+		rental = await getRentalPDA(
+			bundleAccessoryMint.publicKey,
+			artist.publicKey
+		);
+		rentalVault = await getATA(bundleAccessoryMint.publicKey, rental);
+		auction = await getAuctionPDA(
+			accessoryMint.publicKey,
+			collector.publicKey
+		);
+		auctionVault = await getATA(accessoryMint.publicKey, auction);
+		offer = await getOfferPDA(accessoryMint.publicKey, collector.publicKey);
+		collectionOffer = await getCollectionOfferPDA(
+			config,
+			collector.publicKey,
+			collectionOfferId
+		);
 		listing = await getListingPDA(
 			accessoryMint.publicKey,
 			artist.publicKey
 		);
 		listingVault = await getATA(accessoryMint.publicKey, listing);
+		artistMintCounter = await getMintCounterPDA(config, artist.publicKey);
 		artistAccessoryAta = await getATA(
 			accessoryMint.publicKey,
 			artist.publicKey
@@ -135,6 +213,22 @@ describe("Shapely", () => {
 		);
 	});
 
+	async function assertFailsWith(
+		tx: Transaction,
+		signers: Keypair[],
+		errorCode: string
+	) {
+		await assert.rejects(
+			provider.sendAndConfirm(tx, signers),
+			(err: any) => {
+				const logs: string[] = err.logs ?? [];
+				return logs.some((log) =>
+					log.includes(`Error Code: ${errorCode}`)
+				);
+			}
+		);
+	}
+
 	it("Should initialize the avatar and accessory collection", async () => {
 		const modifyComputeUnits = ComputeBudgetProgram.setComputeUnitLimit({
 			units: 400_000,
@@ -178,6 +272,109 @@ describe("Shapely", () => {
 		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
 	});
 
+	it("Should set the accepted payment mints", async () => {
+		const tx = new Transaction().add(
+			await program.methods
+				.setPaymentMints([usdcMint])
+				.accountsStrict({
+					admin: payer.publicKey,
+					config,
+				})
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [payer]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
+	it("Should set the accessory mint cap", async () => {
+		const tx = new Transaction().add(
+			await program.methods
+				.setAccessoryMintCap(10, new BN(24 * 60 * 60))
+				.accountsStrict({
+					admin: payer.publicKey,
+					config,
+				})
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [payer]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
+	it("Should set the swap fee", async () => {
+		const tx = new Transaction().add(
+			await program.methods
+				.setSwapFee(new BN(swapFee))
+				.accountsStrict({
+					admin: payer.publicKey,
+					config,
+				})
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [payer]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
+	it("Should set the referral share", async () => {
+		const tx = new Transaction().add(
+			await program.methods
+				.setReferralBps(referralBps)
+				.accountsStrict({
+					admin: payer.publicKey,
+					config,
+				})
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [payer]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
+	it("Should approve the payer as a referring frontend", async () => {
+		const tx = new Transaction().add(
+			await program.methods
+				.setReferrers([payer.publicKey])
+				.accountsStrict({
+					admin: payer.publicKey,
+					config,
+				})
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [payer]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
+	async function setAvatarPolicyTx(
+		policy: Parameters<typeof program.methods.setAvatarPolicy>[0]
+	): Promise<Transaction> {
+		return new Transaction().add(
+			await program.methods
+				.setAvatarPolicy(policy)
+				.accountsStrict({
+					admin: payer.publicKey,
+					config,
+				})
+				.instruction()
+		);
+	}
+
+	it("Should only require an avatar for primary sales", async () => {
+		const sig = await provider.sendAndConfirm(
+			await setAvatarPolicyTx({ primaryOnly: {} }),
+			[payer]
+		);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
 	it("Should initialize a new accessory mint", async () => {
 		const modifyComputeUnits = ComputeBudgetProgram.setComputeUnitLimit({
 			units: 400_000,
@@ -191,14 +388,26 @@ describe("Shapely", () => {
 			.add(addPriorityFee) // Optional: offer priority fee
 			.add(
 				await program.methods
-					.mintAccessory(accessoryName, accessoryURI)
+					.mintAccessory(
+						accessoryName,
+						accessoryURI,
+						royaltyBps,
+						[
+							{ address: artist.publicKey, share: 70 },
+							{ address: payer.publicKey, share: 30 },
+						],
+						accessorySlot
+					)
 					.accountsStrict({
 						artist: artist.publicKey,
 						artistAccessoryAta,
+						mintCounter: artistMintCounter,
 
 						config,
+						accessoryDrop: null,
 
 						accessoryMint: accessoryMint.publicKey,
+						accessoryRecord,
 						accessoryMetadata,
 						accessoryMasterEdition,
 
@@ -223,6 +432,102 @@ describe("Shapely", () => {
 		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
 	});
 
+	it("Should update the accessory metadata", async () => {
+		const tx = new Transaction().add(
+			await program.methods
+				.updateAccessoryMetadata(accessoryName, accessoryURI)
+				.accountsStrict({
+					artist: artist.publicKey,
+
+					config,
+
+					accessoryMint: accessoryMint.publicKey,
+					accessoryRecord,
+					accessoryMetadata,
+
+					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+				})
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [artist]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
+	async function updateAccessoryMetadataTx(): Promise<Transaction> {
+		return new Transaction().add(
+			await program.methods
+				.updateAccessoryMetadata(accessoryName, accessoryURI)
+				.accountsStrict({
+					artist: artist.publicKey,
+
+					config,
+
+					accessoryMint: accessoryMint.publicKey,
+					accessoryRecord,
+					accessoryMetadata,
+
+					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+				})
+				.instruction()
+		);
+	}
+
+	async function setMetadataUpdatesTx(enabled: boolean): Promise<Transaction> {
+		return new Transaction().add(
+			await program.methods
+				.setMetadataUpdates(enabled)
+				.accountsStrict({
+					admin: payer.publicKey,
+					config,
+				})
+				.instruction()
+		);
+	}
+
+	it("Should reject metadata updates while they are disabled", async () => {
+		await provider.sendAndConfirm(await setMetadataUpdatesTx(false), [payer]);
+
+		await assertFailsWith(
+			await updateAccessoryMetadataTx(),
+			[artist],
+			"MetadataUpdatesDisabled"
+		);
+
+		await provider.sendAndConfirm(await setMetadataUpdatesTx(true), [payer]);
+	});
+
+	it("Should create an avatar drop", async () => {
+		const now = Math.floor(Date.now() / 1000);
+
+		const tx = new Transaction().add(
+			await program.methods
+				.createDrop(
+					{ avatar: {} },
+					{
+						clock: { unixTimestamp: {} },
+						start: new BN(now - 60),
+						end: new BN(now + 60 * 60),
+						supplyCap: new BN(1_000),
+						uriPrefix: "https://www.jsonkeeper.com/",
+						allowlistRoot: null,
+					}
+				)
+				.accountsStrict({
+					admin: payer.publicKey,
+					config,
+					drop: avatarDrop,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [payer]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
 	it("Should initialize a new avatar mint", async () => {
 		const modifyComputeUnits = ComputeBudgetProgram.setComputeUnitLimit({
 			units: 400_000,
@@ -236,12 +541,14 @@ describe("Shapely", () => {
 			.add(addPriorityFee) // Optional: offer priority fee
 			.add(
 				await program.methods
-					.mintAvatar(avatarName, avatarURI)
+					.mintAvatar(avatarName, avatarURI, [])
 					.accountsStrict({
 						collector: collector.publicKey,
 						collectorAvatarAta,
 
 						config,
+						avatarDrop,
+						allowlistClaim: null,
 
 						avatarMint,
 						avatarMetadata,
@@ -268,12 +575,107 @@ describe("Shapely", () => {
 		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
 	});
 
+	async function mintAvatarTx(
+		wallet: PublicKey,
+		proof: number[][],
+		allowlistClaim: PublicKey | null
+	): Promise<Transaction> {
+		const mint = await getAvatarMintPDA(wallet, avatarCollection);
+
+		return new Transaction()
+			.add(ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }))
+			.add(
+				await program.methods
+					.mintAvatar(avatarName, avatarURI, proof)
+					.accountsStrict({
+						collector: wallet,
+						collectorAvatarAta: await getATA(mint, wallet),
+
+						config,
+						avatarDrop,
+						allowlistClaim,
+
+						avatarMint: mint,
+						avatarMetadata: await getMetadataAccount(mint),
+						avatarMasterEdition: await getMasterEdition(mint),
+
+						avatarCollection,
+						avatarCollectionMetadata,
+						avatarCollectionMasterEdition,
+
+						sysvarInstruction: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+
+						metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+						tokenProgram: TOKEN_PROGRAM_ADDRESS,
+						associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+						systemProgram: SYSTEM_PROGRAM_ADDRESS,
+					})
+					.instruction()
+			);
+	}
+
+	it("Should mint an avatar from the drop allowlist", async () => {
+		// The collector already minted, the payer is the allowlisted wallet
+		const payerLeaf = allowlistLeaf(payer.publicKey);
+		const collectorLeaf = allowlistLeaf(collector.publicKey);
+		const root = allowlistNode(payerLeaf, collectorLeaf);
+
+		await provider.sendAndConfirm(
+			new Transaction().add(
+				await program.methods
+					.setDropAllowlist([...root])
+					.accountsStrict({
+						admin: payer.publicKey,
+						drop: avatarDrop,
+						config,
+					})
+					.instruction()
+			),
+			[payer]
+		);
+
+		// The first avatar drop of the config has id 1
+		const allowlistClaim = await getAllowlistClaimPDA(
+			config,
+			1,
+			payer.publicKey
+		);
+
+		const sig = await provider.sendAndConfirm(
+			await mintAvatarTx(payer.publicKey, [[...collectorLeaf]], allowlistClaim),
+			[payer]
+		);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+
+		const claim = await program.account.allowlistClaim.fetch(allowlistClaim);
+		assert.equal(claim.dropId.toNumber(), 1);
+	});
+
+	it("Should reject avatar mints from wallets outside the allowlist", async () => {
+		const allowlistClaim = await getAllowlistClaimPDA(
+			config,
+			1,
+			artist.publicKey
+		);
+
+		await assertFailsWith(
+			await mintAvatarTx(
+				artist.publicKey,
+				[[...allowlistLeaf(collector.publicKey)]],
+				allowlistClaim
+			),
+			[artist],
+			"NotAllowlisted"
+		);
+	});
+
 	it("Should list an accessory", async () => {
 		const accessoryPrice = 0.01 * LAMPORTS_PER_SOL;
 
 		const tx = new Transaction().add(
 			await program.methods
-				.listAccessory(new BN(accessoryPrice))
+				.listAccessory(new BN(accessoryPrice), null, null, null)
 				.accountsStrict({
 					artist: artist.publicKey,
 					artistAccessoryAta,
@@ -281,6 +683,7 @@ describe("Shapely", () => {
 					config,
 					listing,
 					listingVault,
+					sellerProceeds: null,
 
 					accessoryMint: accessoryMint.publicKey,
 					accessoryMetadata,
@@ -313,7 +716,9 @@ describe("Shapely", () => {
 					listingVault,
 
 					accessoryMint: accessoryMint.publicKey,
+					accessoryMasterEdition: null,
 
+					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
 					tokenProgram: TOKEN_PROGRAM_ADDRESS,
 					systemProgram: SYSTEM_PROGRAM_ADDRESS,
 				})
@@ -325,19 +730,19 @@ describe("Shapely", () => {
 		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
 	});
 
-	it("Should relist an accessory", async () => {
+	it("Should list an accessory without escrow", async () => {
 		const accessoryPrice = 0.01 * LAMPORTS_PER_SOL;
 
 		const tx = new Transaction().add(
 			await program.methods
-				.listAccessory(new BN(accessoryPrice))
+				.listAccessoryDelegated(new BN(accessoryPrice), null, null, null)
 				.accountsStrict({
 					artist: artist.publicKey,
 					artistAccessoryAta,
 
 					config,
 					listing,
-					listingVault,
+					sellerProceeds: null,
 
 					accessoryMint: accessoryMint.publicKey,
 					accessoryMetadata,
@@ -346,7 +751,6 @@ describe("Shapely", () => {
 
 					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
 					tokenProgram: TOKEN_PROGRAM_ADDRESS,
-					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
 					systemProgram: SYSTEM_PROGRAM_ADDRESS,
 				})
 				.instruction()
@@ -357,34 +761,2625 @@ describe("Shapely", () => {
 		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
 	});
 
-	it("Should buy a listed accessory", async () => {
+	it("Should delist an accessory listed without escrow", async () => {
 		const tx = new Transaction().add(
 			await program.methods
-				.buyAccessory()
+				.delistAccessory()
 				.accountsStrict({
-					collector: collector.publicKey,
-					collectorAvatarMint: avatarMint,
-					collectorAvatarMetadata: avatarMetadata,
-					collectorAccessoryAta,
+					artist: artist.publicKey,
+					artistAccessoryAta,
 
 					config,
-					treasury,
 					listing,
-					listingVault,
+					listingVault: null,
+
+					accessoryMint: accessoryMint.publicKey,
+					accessoryMasterEdition,
+
+					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [artist]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
+	it("Should relist an accessory", async () => {
+		const accessoryPrice = 0.01 * LAMPORTS_PER_SOL;
+		const expiresAt = Math.floor(Date.now() / 1000) + 24 * 60 * 60;
 
+		const tx = new Transaction().add(
+			await program.methods
+				.listAccessory(new BN(accessoryPrice), null, new BN(expiresAt), null)
+				.accountsStrict({
 					artist: artist.publicKey,
+					artistAccessoryAta,
+
+					config,
+					listing,
+					listingVault,
+					sellerProceeds,
+
 					accessoryMint: accessoryMint.publicKey,
+					accessoryMetadata,
+					accessoryCollection,
+					accessoryMasterEdition,
 
+					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
 					tokenProgram: TOKEN_PROGRAM_ADDRESS,
 					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
-					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
 					systemProgram: SYSTEM_PROGRAM_ADDRESS,
 				})
 				.instruction()
 		);
 
-		const sig = await provider.sendAndConfirm(tx, [collector]);
+		const sig = await provider.sendAndConfirm(tx, [artist]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
+	it("Should update the listing price", async () => {
+		const accessoryPrice = 0.02 * LAMPORTS_PER_SOL;
+
+		const tx = new Transaction().add(
+			await program.methods
+				.updateListingPrice(new BN(accessoryPrice))
+				.accountsStrict({
+					artist: artist.publicKey,
+					listing,
+					accessoryMint: accessoryMint.publicKey,
+				})
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [artist]);
 
 		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
 	});
+
+	it("Should read the current listing price", async () => {
+		const price = await program.methods
+			.getListingPrice()
+			.accountsStrict({
+				artist: artist.publicKey,
+				listing,
+				accessoryMint: accessoryMint.publicKey,
+			})
+			.view();
+
+		assert.equal(price.toNumber(), 0.02 * LAMPORTS_PER_SOL);
+	});
+
+	it("Should make an offer on an accessory", async () => {
+		const offerAmount = 0.005 * LAMPORTS_PER_SOL;
+
+		const tx = new Transaction().add(
+			await program.methods
+				.makeOffer(new BN(offerAmount))
+				.accountsStrict({
+					buyer: collector.publicKey,
+
+					config,
+					offer,
+
+					accessoryMint: accessoryMint.publicKey,
+					accessoryMetadata,
+
+					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [collector]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
+	it("Should cancel an offer", async () => {
+		const tx = new Transaction().add(
+			await program.methods
+				.cancelOffer()
+				.accountsStrict({
+					buyer: collector.publicKey,
+					offer,
+					accessoryMint: accessoryMint.publicKey,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [collector]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
+	for (const [description, mint, errorCode] of [
+		[
+			"an accessory from another collection",
+			() => avatarMint,
+			"AccessoryWrongCollection",
+		],
+		[
+			"a mint without a collection",
+			() => accessoryCollection,
+			"AccessoryMissingCollection",
+		],
+	] as const) {
+		it(`Should reject an offer on ${description}`, async () => {
+			const unaffiliatedMint = mint();
+
+			const tx = new Transaction().add(
+				await program.methods
+					.makeOffer(new BN(0.005 * LAMPORTS_PER_SOL))
+					.accountsStrict({
+						buyer: collector.publicKey,
+
+						config,
+						offer: await getOfferPDA(
+							unaffiliatedMint,
+							collector.publicKey
+						),
+
+						accessoryMint: unaffiliatedMint,
+						accessoryMetadata:
+							await getMetadataAccount(unaffiliatedMint),
+
+						metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+						systemProgram: SYSTEM_PROGRAM_ADDRESS,
+					})
+					.instruction()
+			);
+
+			await assertFailsWith(tx, [collector], errorCode);
+		});
+	}
+
+	it("Should make a collection offer", async () => {
+		const offerPrice = 0.001 * LAMPORTS_PER_SOL;
+
+		const tx = new Transaction().add(
+			await program.methods
+				.makeCollectionOffer(
+					new BN(collectionOfferId),
+					{ artist: { artist: artist.publicKey } },
+					new BN(offerPrice),
+					3
+				)
+				.accountsStrict({
+					buyer: collector.publicKey,
+					config,
+					collectionOffer,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [collector]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
+	it("Should cancel a collection offer", async () => {
+		const tx = new Transaction().add(
+			await program.methods
+				.cancelCollectionOffer()
+				.accountsStrict({
+					buyer: collector.publicKey,
+					config,
+					collectionOffer,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [collector]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
+	async function buyListedAccessoryTx({
+		collectorAvatarMetadata = avatarMetadata,
+		referrer = payer.publicKey,
+	}: {
+		collectorAvatarMetadata?: PublicKey;
+		referrer?: PublicKey;
+	} = {}): Promise<Transaction> {
+		const expectedPrice = 0.02 * LAMPORTS_PER_SOL;
+
+		return new Transaction().add(
+			await program.methods
+				.buyAccessory(new BN(expectedPrice), null)
+				.accountsStrict({
+					collector: collector.publicKey,
+					collectorAvatarMint: avatarMint,
+					collectorAvatarMetadata,
+					collectorAccessoryAta,
+
+					config,
+					treasury,
+					listing,
+					listingVault,
+					artistAccessoryAta: null,
+					accessoryMasterEdition: null,
+					sellerProceeds,
+
+					artist: artist.publicKey,
+					artistPayout: null,
+					accessoryMint: accessoryMint.publicKey,
+					accessoryRecord,
+
+					paymentMint: null,
+					collectorPaymentAta: null,
+					artistPaymentAta: null,
+					treasuryPaymentAta: null,
+
+					referrer,
+					referrerPaymentAta: null,
+
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				// One account per creator in the accessory record
+				.remainingAccounts([
+					{ pubkey: artist.publicKey, isSigner: false, isWritable: true },
+					{ pubkey: payer.publicKey, isSigner: false, isWritable: true },
+				])
+				.instruction()
+		);
+	}
+
+	it("Should reject a purchase with avatar metadata of another mint", async () => {
+		await assertFailsWith(
+			// Metadata of an NFT that is not the collector's avatar
+			await buyListedAccessoryTx({
+				collectorAvatarMetadata: accessoryCollectionMetadata,
+			}),
+			[collector],
+			"MetadataMintMismatch"
+		);
+	});
+
+	it("Should reject buyers referring their own purchase", async () => {
+		await assertFailsWith(
+			await buyListedAccessoryTx({ referrer: collector.publicKey }),
+			[collector],
+			"InvalidReferrer"
+		);
+	});
+
+	it("Should reject referrers that are not approved", async () => {
+		await assertFailsWith(
+			await buyListedAccessoryTx({ referrer: Keypair.generate().publicKey }),
+			[collector],
+			"InvalidReferrer"
+		);
+	});
+
+	it("Should buy a listed accessory", async () => {
+		const listingRent = await provider.connection.getBalance(listing);
+		const vaultRent = await provider.connection.getBalance(listingVault);
+		const proceedsBefore = await program.account.proceeds.fetch(sellerProceeds);
+
+		const sig = await provider.sendAndConfirm(await buyListedAccessoryTx(), [
+			collector,
+		]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+
+		// The listing and vault rent are refunded to the seller's proceeds, not their wallet,
+		// and are claimable without being counted as sale income
+		const proceeds = await program.account.proceeds.fetch(sellerProceeds);
+		const credited =
+			proceeds.totalCredited.toNumber() -
+			proceedsBefore.totalCredited.toNumber();
+		const rentRefunded =
+			proceeds.totalRentRefunded.toNumber() -
+			proceedsBefore.totalRentRefunded.toNumber();
+
+		assert.equal(rentRefunded, listingRent + vaultRent);
+		assert.equal(
+			proceeds.claimable.toNumber() - proceedsBefore.claimable.toNumber(),
+			credited + rentRefunded
+		);
+		assert.strictEqual(await provider.connection.getAccountInfo(listing), null);
+	});
+
+	it("Should reject metadata updates once the accessory is sold", async () => {
+		// The first sale locks the accessory metadata
+		await assertFailsWith(
+			await updateAccessoryMetadataTx(),
+			[artist],
+			"MetadataLocked"
+		);
+	});
+
+	it("Should claim the seller's proceeds", async () => {
+		const tx = new Transaction().add(
+			await program.methods
+				.claimProceeds()
+				.accountsStrict({
+					seller: artist.publicKey,
+					sellerProceeds,
+				})
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [artist]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+
+		const proceeds = await program.account.proceeds.fetch(sellerProceeds);
+
+		assert.strictEqual(proceeds.claimable.toNumber(), 0);
+		assert.strictEqual(
+			proceeds.totalClaimed.toNumber(),
+			proceeds.totalCredited.toNumber() + proceeds.totalRentRefunded.toNumber()
+		);
+	});
+
+	it("Should auction a bought accessory", async () => {
+		const reservePrice = 0.01 * LAMPORTS_PER_SOL;
+		const minIncrement = 0.001 * LAMPORTS_PER_SOL;
+		const endTime = Math.floor(Date.now() / 1000) + 20;
+
+		const tx = new Transaction().add(
+			await program.methods
+				.createAuction(
+					new BN(reservePrice),
+					new BN(minIncrement),
+					new BN(endTime)
+				)
+				.accountsStrict({
+					seller: collector.publicKey,
+					sellerAccessoryAta: collectorAccessoryAta,
+
+					config,
+					auction,
+					auctionVault,
+
+					accessoryMint: accessoryMint.publicKey,
+					accessoryMetadata,
+
+					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [collector]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
+	it("Should place a bid on an auction", async () => {
+		const bid = 0.01 * LAMPORTS_PER_SOL;
+
+		const tx = new Transaction().add(
+			await program.methods
+				.placeBid(new BN(bid))
+				.accountsStrict({
+					bidder: collector.publicKey,
+					bidderAvatarMint: avatarMint,
+					bidderAvatarMetadata: avatarMetadata,
+
+					config,
+					auction,
+					previousBidder: null,
+
+					seller: collector.publicKey,
+					accessoryMint: accessoryMint.publicKey,
+					accessoryRecord,
+
+					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [collector]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
+	it("Should settle an ended auction", async () => {
+		// Wait for the auction to end
+		await new Promise((resolve) => setTimeout(resolve, 25_000));
+
+		const connection = provider.connection;
+		const { highestBid } = await program.account.auction.fetch(auction);
+		const bid = highestBid.toNumber();
+		const auctionBalance = await connection.getBalance(auction);
+		const vaultRent = await connection.getBalance(auctionVault);
+		const treasuryBalance = await connection.getBalance(treasury);
+		const artistBalance = await connection.getBalance(artist.publicKey);
+		const payerBalance = await connection.getBalance(payer.publicKey);
+		const collectorBalance = await connection.getBalance(collector.publicKey);
+
+		const tx = new Transaction().add(
+			await program.methods
+				.settleAuction()
+				.accountsStrict({
+					cranker: payer.publicKey,
+					seller: collector.publicKey,
+					winner: collector.publicKey,
+					winnerAccessoryAta: collectorAccessoryAta,
+
+					config,
+					treasury,
+					auction,
+					auctionVault,
+
+					accessoryMint: accessoryMint.publicKey,
+					accessoryRecord,
+
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				// One account per creator in the accessory record
+				.remainingAccounts([
+					{ pubkey: artist.publicKey, isSigner: false, isWritable: true },
+					{ pubkey: payer.publicKey, isSigner: false, isWritable: true },
+				])
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [payer]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+
+		// The collector resells the accessory, so the royalty is split 70/30
+		// between the artist and the payer, with the rounding dust to the artist
+		const feeAmount = Math.floor((bid * fee) / 10_000);
+		const royalty = Math.floor((bid * royaltyBps) / 10_000);
+		const payerRoyalty = Math.floor((royalty * 30) / 100);
+
+		assert.equal(
+			(await connection.getBalance(treasury)) - treasuryBalance,
+			feeAmount
+		);
+		assert.equal(
+			(await connection.getBalance(artist.publicKey)) - artistBalance,
+			royalty - payerRoyalty
+		);
+		assert.equal(
+			(await connection.getBalance(payer.publicKey)) - payerBalance,
+			payerRoyalty - (await feePaidBy(sig, payer.publicKey))
+		);
+		// The auction and vault rent go back to the seller with their payout
+		assert.equal(
+			(await connection.getBalance(collector.publicKey)) - collectorBalance,
+			auctionBalance - feeAmount - royalty + vaultRent
+		);
+		assert.equal(await connection.getAccountInfo(auction), null);
+	});
+
+	it("Should mint an accessory for a bundle", async () => {
+		const modifyComputeUnits = ComputeBudgetProgram.setComputeUnitLimit({
+			units: 400_000,
+		});
+
+		const tx = new Transaction().add(modifyComputeUnits).add(
+			await program.methods
+				.mintAccessory(
+					accessoryName,
+					accessoryURI,
+					royaltyBps,
+					[],
+					accessorySlot
+				)
+				.accountsStrict({
+					artist: artist.publicKey,
+					artistAccessoryAta: await getATA(
+						bundleAccessoryMint.publicKey,
+						artist.publicKey
+					),
+					mintCounter: artistMintCounter,
+
+					config,
+					accessoryDrop: null,
+
+					accessoryMint: bundleAccessoryMint.publicKey,
+					accessoryRecord: bundleAccessoryRecord,
+					accessoryMetadata: await getMetadataAccount(
+						bundleAccessoryMint.publicKey
+					),
+					accessoryMasterEdition: await getMasterEdition(
+						bundleAccessoryMint.publicKey
+					),
+
+					accessoryCollection,
+					accessoryCollectionMetadata,
+					accessoryCollectionMasterEdition,
+
+					sysvarInstruction: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+
+					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [
+			artist,
+			bundleAccessoryMint,
+		]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
+	it("Should list a bundle of accessories", async () => {
+		const bundlePrice = 0.05 * LAMPORTS_PER_SOL;
+
+		const tx = new Transaction().add(
+			await program.methods
+				.listBundle(new BN(bundleId), new BN(bundlePrice))
+				.accountsStrict({
+					seller: artist.publicKey,
+					bundle,
+					config,
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.remainingAccounts([
+					{
+						pubkey: bundleAccessoryMint.publicKey,
+						isSigner: false,
+						isWritable: false,
+					},
+					{
+						pubkey: bundleAccessoryRecord,
+						isSigner: false,
+						isWritable: false,
+					},
+					{
+						pubkey: await getATA(
+							bundleAccessoryMint.publicKey,
+							artist.publicKey
+						),
+						isSigner: false,
+						isWritable: true,
+					},
+					{
+						pubkey: await getATA(bundleAccessoryMint.publicKey, bundle),
+						isSigner: false,
+						isWritable: true,
+					},
+				])
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [artist]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
+	it("Should delist a bundle of accessories", async () => {
+		const tx = new Transaction().add(
+			await program.methods
+				.delistBundle()
+				.accountsStrict({
+					seller: artist.publicKey,
+					bundle,
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.remainingAccounts([
+					{
+						pubkey: bundleAccessoryMint.publicKey,
+						isSigner: false,
+						isWritable: false,
+					},
+					{
+						pubkey: await getATA(bundleAccessoryMint.publicKey, bundle),
+						isSigner: false,
+						isWritable: true,
+					},
+					{
+						pubkey: await getATA(
+							bundleAccessoryMint.publicKey,
+							artist.publicKey
+						),
+						isSigner: false,
+						isWritable: true,
+					},
+				])
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [artist]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
+	it("Should offer an accessory in a swap", async () => {
+		const tx = new Transaction().add(
+			await program.methods
+				.makeSwap(
+					new BN(swapId),
+					[accessoryMint.publicKey],
+					new BN(0.01 * LAMPORTS_PER_SOL)
+				)
+				.accountsStrict({
+					maker: artist.publicKey,
+					swap,
+					config,
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.remainingAccounts([
+					{
+						pubkey: bundleAccessoryMint.publicKey,
+						isSigner: false,
+						isWritable: false,
+					},
+					{
+						pubkey: bundleAccessoryMetadata,
+						isSigner: false,
+						isWritable: false,
+					},
+					{
+						pubkey: await getATA(
+							bundleAccessoryMint.publicKey,
+							artist.publicKey
+						),
+						isSigner: false,
+						isWritable: true,
+					},
+					{
+						pubkey: await getATA(bundleAccessoryMint.publicKey, swap),
+						isSigner: false,
+						isWritable: true,
+					},
+				])
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [artist]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
+	it("Should cancel a swap", async () => {
+		const tx = new Transaction().add(
+			await program.methods
+				.cancelSwap()
+				.accountsStrict({
+					maker: artist.publicKey,
+					swap,
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.remainingAccounts([
+					{
+						pubkey: bundleAccessoryMint.publicKey,
+						isSigner: false,
+						isWritable: false,
+					},
+					{
+						pubkey: await getATA(bundleAccessoryMint.publicKey, swap),
+						isSigner: false,
+						isWritable: true,
+					},
+					{
+						pubkey: await getATA(
+							bundleAccessoryMint.publicKey,
+							artist.publicKey
+						),
+						isSigner: false,
+						isWritable: true,
+					},
+				])
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [artist]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
+	it("Should list an accessory for rent", async () => {
+		const pricePerPeriod = 0.001 * LAMPORTS_PER_SOL;
+		const period = 5; // seconds
+
+		const tx = new Transaction().add(
+			await program.methods
+				.listRental(new BN(pricePerPeriod), new BN(period), 3)
+				.accountsStrict({
+					owner: artist.publicKey,
+					ownerAccessoryAta: await getATA(
+						bundleAccessoryMint.publicKey,
+						artist.publicKey
+					),
+					accessoryMint: bundleAccessoryMint.publicKey,
+					accessoryMetadata: bundleAccessoryMetadata,
+					rental,
+					rentalVault,
+					config,
+
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [artist]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
+	it("Should rent an accessory", async () => {
+		const pricePerPeriod = 0.001 * LAMPORTS_PER_SOL;
+
+		const tx = new Transaction().add(
+			await program.methods
+				.rentAccessory(1, new BN(pricePerPeriod))
+				.accountsStrict({
+					renter: collector.publicKey,
+					owner: artist.publicKey,
+					accessoryMint: bundleAccessoryMint.publicKey,
+					rental,
+					rentalVault,
+					renterAccessoryAta: await getATA(
+						bundleAccessoryMint.publicKey,
+						collector.publicKey
+					),
+					accessoryMasterEdition: await getMasterEdition(
+						bundleAccessoryMint.publicKey
+					),
+					config,
+					treasury,
+
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [collector]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
+	it("Should reclaim an accessory once the rental ends", async () => {
+		// Wait for the rental period to pass
+		await new Promise((resolve) => setTimeout(resolve, 10_000));
+
+		const tx = new Transaction().add(
+			await program.methods
+				.reclaimRental()
+				.accountsStrict({
+					cranker: payer.publicKey,
+					owner: artist.publicKey,
+					renter: collector.publicKey,
+					accessoryMint: bundleAccessoryMint.publicKey,
+					rental,
+					rentalVault,
+					renterAccessoryAta: await getATA(
+						bundleAccessoryMint.publicKey,
+						collector.publicKey
+					),
+					accessoryMasterEdition: await getMasterEdition(
+						bundleAccessoryMint.publicKey
+					),
+
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+				})
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [payer]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
+	it("Should delist a rental", async () => {
+		const tx = new Transaction().add(
+			await program.methods
+				.delistRental()
+				.accountsStrict({
+					owner: artist.publicKey,
+					ownerAccessoryAta: await getATA(
+						bundleAccessoryMint.publicKey,
+						artist.publicKey
+					),
+					accessoryMint: bundleAccessoryMint.publicKey,
+					rental,
+					rentalVault,
+
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [artist]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
+	it("Should gift an accessory to an avatar", async () => {
+		const tx = new Transaction().add(
+			await program.methods
+				.giftAccessory("Happy birthday!")
+				.accountsStrict({
+					sender: artist.publicKey,
+					recipient: collector.publicKey,
+					avatarMint,
+					avatarMetadata,
+					recipientAvatarAta: collectorAvatarAta,
+
+					accessoryMint: bundleAccessoryMint.publicKey,
+					accessoryMetadata: bundleAccessoryMetadata,
+					senderAccessoryAta: await getATA(
+						bundleAccessoryMint.publicKey,
+						artist.publicKey
+					),
+					recipientAccessoryAta: await getATA(
+						bundleAccessoryMint.publicKey,
+						collector.publicKey
+					),
+
+					config,
+
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [artist]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
+	it("Should reject gifting an accessory the sender no longer holds", async () => {
+		const tx = new Transaction().add(
+			await program.methods
+				.giftAccessory("Happy birthday!")
+				.accountsStrict({
+					sender: artist.publicKey,
+					recipient: collector.publicKey,
+					avatarMint,
+					avatarMetadata,
+					recipientAvatarAta: collectorAvatarAta,
+
+					accessoryMint: bundleAccessoryMint.publicKey,
+					accessoryMetadata: bundleAccessoryMetadata,
+					senderAccessoryAta: await getATA(
+						bundleAccessoryMint.publicKey,
+						artist.publicKey
+					),
+					recipientAccessoryAta: await getATA(
+						bundleAccessoryMint.publicKey,
+						collector.publicKey
+					),
+
+					config,
+
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.instruction()
+		);
+
+		// The artist's token account is empty after the first gift
+		await assertFailsWith(tx, [artist], "AccessoryNotHeld");
+	});
+
+	async function mintAccessoryTx(
+		mint: Keypair,
+		drop: PublicKey | null,
+		slot: number = accessorySlot,
+		creators: { address: PublicKey; share: number }[] = []
+	): Promise<Transaction> {
+		return new Transaction()
+			.add(ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }))
+			.add(
+				await program.methods
+					.mintAccessory(accessoryName, accessoryURI, royaltyBps, creators, slot)
+					.accountsStrict({
+						artist: artist.publicKey,
+						artistAccessoryAta: await getATA(mint.publicKey, artist.publicKey),
+						mintCounter: artistMintCounter,
+
+						config,
+						accessoryDrop: drop,
+
+						accessoryMint: mint.publicKey,
+						accessoryRecord: await getAccessoryRecordPDA(mint.publicKey),
+						accessoryMetadata: await getMetadataAccount(mint.publicKey),
+						accessoryMasterEdition: await getMasterEdition(mint.publicKey),
+
+						accessoryCollection,
+						accessoryCollectionMetadata,
+						accessoryCollectionMasterEdition,
+
+						sysvarInstruction: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+
+						metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+						tokenProgram: TOKEN_PROGRAM_ADDRESS,
+						associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+						systemProgram: SYSTEM_PROGRAM_ADDRESS,
+					})
+					.instruction()
+			);
+	}
+
+	async function mintAndListAccessory(
+		price: number,
+		{
+			reservedBuyer = null,
+			paymentMint = null,
+			expiresAt = null,
+			dutchAuction = null,
+			creators = [],
+		}: {
+			reservedBuyer?: PublicKey | null;
+			paymentMint?: PublicKey | null;
+			expiresAt?: number | null;
+			dutchAuction?: any | null;
+			creators?: { address: PublicKey; share: number }[];
+		} = {}
+	): Promise<Keypair> {
+		const mint = Keypair.generate();
+
+		await provider.sendAndConfirm(
+			await mintAccessoryTx(mint, null, accessorySlot, creators),
+			[artist, mint]
+		);
+
+		const listing = await getListingPDA(mint.publicKey, artist.publicKey);
+
+		const expiry = expiresAt === null ? null : new BN(expiresAt);
+		const listMethod = dutchAuction
+			? program.methods.listAccessoryDutch(
+					dutchAuction,
+					paymentMint,
+					expiry,
+					reservedBuyer
+				)
+			: program.methods.listAccessory(
+					new BN(price),
+					paymentMint,
+					expiry,
+					reservedBuyer
+				);
+
+		const listTx = new Transaction().add(
+			await listMethod
+				.accountsStrict({
+					artist: artist.publicKey,
+					artistAccessoryAta: await getATA(mint.publicKey, artist.publicKey),
+
+					config,
+					listing,
+					listingVault: await getATA(mint.publicKey, listing),
+					sellerProceeds: null,
+
+					accessoryMint: mint.publicKey,
+					accessoryMetadata: await getMetadataAccount(mint.publicKey),
+					accessoryCollection,
+					accessoryMasterEdition: await getMasterEdition(mint.publicKey),
+
+					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.instruction()
+		);
+
+		await provider.sendAndConfirm(listTx, [artist]);
+
+		return mint;
+	}
+
+	async function setArtistMintCapTx(cap: number | null): Promise<Transaction> {
+		return new Transaction().add(
+			await program.methods
+				.setArtistMintCap(cap)
+				.accountsStrict({
+					admin: payer.publicKey,
+					artist: artist.publicKey,
+					mintCounter: artistMintCounter,
+					config,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.instruction()
+		);
+	}
+
+	it("Should stop minting at the artist's cap until the admin raises it", async () => {
+		const { minted } = await program.account.mintCounter.fetch(
+			artistMintCounter
+		);
+
+		// Leave room for exactly one more mint
+		await provider.sendAndConfirm(await setArtistMintCapTx(minted + 1), [
+			payer,
+		]);
+
+		const mint = Keypair.generate();
+		await provider.sendAndConfirm(await mintAccessoryTx(mint, null), [
+			artist,
+			mint,
+		]);
+
+		const cappedMint = Keypair.generate();
+		await assertFailsWith(
+			await mintAccessoryTx(cappedMint, null),
+			[artist, cappedMint],
+			"MintCapReached"
+		);
+
+		await provider.sendAndConfirm(await setArtistMintCapTx(minted + 2), [
+			payer,
+		]);
+
+		const sig = await provider.sendAndConfirm(
+			await mintAccessoryTx(cappedMint, null),
+			[artist, cappedMint]
+		);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+
+		assert.equal(
+			(await program.account.mintCounter.fetch(artistMintCounter)).minted,
+			minted + 2
+		);
+
+		// Fall back to the config cap for the tests below
+		await provider.sendAndConfirm(await setArtistMintCapTx(null), [payer]);
+	});
+
+	it("Should lift the accessory mint cap for cart checkouts", async () => {
+		const tx = new Transaction().add(
+			await program.methods
+				.setAccessoryMintCap(0, new BN(0))
+				.accountsStrict({
+					admin: payer.publicKey,
+					config,
+				})
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [payer]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+	});
+
+	// Transaction fee charged to `wallet` if it paid for the transaction
+	async function feePaidBy(sig: string, wallet: PublicKey): Promise<number> {
+		const { meta, transaction } = await provider.connection.getTransaction(
+			sig,
+			{ commitment: "confirmed", maxSupportedTransactionVersion: 0 }
+		);
+
+		return transaction.message.staticAccountKeys[0].equals(wallet)
+			? meta.fee
+			: 0;
+	}
+
+	async function makeOfferTx(
+		mint: PublicKey,
+		buyer: Keypair,
+		amount: number
+	): Promise<Transaction> {
+		return new Transaction().add(
+			await program.methods
+				.makeOffer(new BN(amount))
+				.accountsStrict({
+					buyer: buyer.publicKey,
+
+					config,
+					offer: await getOfferPDA(mint, buyer.publicKey),
+
+					accessoryMint: mint,
+					accessoryMetadata: await getMetadataAccount(mint),
+
+					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.instruction()
+		);
+	}
+
+	// The artist accepts the buyer's offer, `buyerAvatarMint` is left out when null
+	async function acceptOfferTx(
+		mint: PublicKey,
+		buyer: Keypair,
+		buyerAvatarMint: PublicKey | null
+	): Promise<Transaction> {
+		return new Transaction().add(
+			await program.methods
+				.acceptOffer()
+				.accountsStrict({
+					seller: artist.publicKey,
+					buyer: buyer.publicKey,
+					buyerAvatarMint,
+					buyerAvatarMetadata: buyerAvatarMint
+						? await getMetadataAccount(buyerAvatarMint)
+						: null,
+
+					accessoryMint: mint,
+					accessoryRecord: await getAccessoryRecordPDA(mint),
+					sellerAccessoryAta: await getATA(mint, artist.publicKey),
+					buyerAccessoryAta: await getATA(mint, buyer.publicKey),
+					offer: await getOfferPDA(mint, buyer.publicKey),
+
+					config,
+					treasury,
+
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				// Accessories minted without creators only have the artist as creator
+				.remainingAccounts([
+					{ pubkey: artist.publicKey, isSigner: false, isWritable: true },
+				])
+				.instruction()
+		);
+	}
+
+	it("Should accept an offer on an accessory", async () => {
+		const amount = 0.01 * LAMPORTS_PER_SOL;
+
+		const mint = Keypair.generate();
+		await provider.sendAndConfirm(await mintAccessoryTx(mint, null), [
+			artist,
+			mint,
+		]);
+
+		const offer = await getOfferPDA(mint.publicKey, collector.publicKey);
+		const buyerAccessoryAta = await getATA(mint.publicKey, collector.publicKey);
+
+		await provider.sendAndConfirm(
+			await makeOfferTx(mint.publicKey, collector, amount),
+			[collector]
+		);
+
+		const connection = provider.connection;
+		const offerBalance = await connection.getBalance(offer);
+		const artistBalance = await connection.getBalance(artist.publicKey);
+		const collectorBalance = await connection.getBalance(collector.publicKey);
+		const treasuryBalance = await connection.getBalance(treasury);
+		const ataRent = await connection.getMinimumBalanceForRentExemption(165);
+
+		const sig = await provider.sendAndConfirm(
+			await acceptOfferTx(mint.publicKey, collector, avatarMint),
+			[artist]
+		);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+
+		// The artist sells their own accessory, so no royalty is owed
+		const feeAmount = Math.floor((amount * fee) / 10_000);
+
+		assert.equal(
+			(await connection.getBalance(treasury)) - treasuryBalance,
+			feeAmount
+		);
+		assert.equal(
+			(await connection.getBalance(artist.publicKey)) - artistBalance,
+			amount - feeAmount - ataRent - (await feePaidBy(sig, artist.publicKey))
+		);
+		assert.equal(
+			(await connection.getBalance(collector.publicKey)) - collectorBalance,
+			offerBalance - amount
+		);
+		assert.equal(await connection.getAccountInfo(offer), null);
+		assert.equal(
+			(await connection.getTokenAccountBalance(buyerAccessoryAta)).value
+				.amount,
+			"1"
+		);
+	});
+
+	it("Should apply the avatar policy to buyers without an avatar", async () => {
+		const amount = 0.001 * LAMPORTS_PER_SOL;
+		const connection = provider.connection;
+
+		// A fresh wallet that never minted an avatar
+		const buyer = Keypair.generate();
+		await provider.sendAndConfirm(
+			new Transaction().add(
+				SystemProgram.transfer({
+					fromPubkey: payer.publicKey,
+					toPubkey: buyer.publicKey,
+					lamports: 0.01 * LAMPORTS_PER_SOL,
+				})
+			),
+			[payer]
+		);
+
+		const mint = Keypair.generate();
+		await provider.sendAndConfirm(await mintAccessoryTx(mint, null), [
+			artist,
+			mint,
+		]);
+		await provider.sendAndConfirm(
+			await makeOfferTx(mint.publicKey, buyer, amount),
+			[buyer]
+		);
+
+		await provider.sendAndConfirm(
+			await setAvatarPolicyTx({ required: {} }),
+			[payer]
+		);
+
+		await assertFailsWith(
+			await acceptOfferTx(mint.publicKey, buyer, null),
+			[artist],
+			"AvatarRequired"
+		);
+
+		await provider.sendAndConfirm(
+			await setAvatarPolicyTx({ notRequired: {} }),
+			[payer]
+		);
+
+		const sig = await provider.sendAndConfirm(
+			await acceptOfferTx(mint.publicKey, buyer, null),
+			[artist]
+		);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+
+		assert.equal(
+			(
+				await connection.getTokenAccountBalance(
+					await getATA(mint.publicKey, buyer.publicKey)
+				)
+			).value.amount,
+			"1"
+		);
+
+		await provider.sendAndConfirm(
+			await setAvatarPolicyTx({ primaryOnly: {} }),
+			[payer]
+		);
+	});
+
+	it("Should fill a slot-wide collection offer", async () => {
+		const price = 0.001 * LAMPORTS_PER_SOL;
+		const slot = 3;
+		const offerId = collectionOfferId + 1;
+
+		const slotOffer = await getCollectionOfferPDA(
+			config,
+			collector.publicKey,
+			offerId
+		);
+
+		await provider.sendAndConfirm(
+			new Transaction().add(
+				await program.methods
+					.makeCollectionOffer(
+						new BN(offerId),
+						{ slot: { slot } },
+						new BN(price),
+						1
+					)
+					.accountsStrict({
+						buyer: collector.publicKey,
+						config,
+						collectionOffer: slotOffer,
+						systemProgram: SYSTEM_PROGRAM_ADDRESS,
+					})
+					.instruction()
+			),
+			[collector]
+		);
+
+		const mint = Keypair.generate();
+		await provider.sendAndConfirm(await mintAccessoryTx(mint, null, slot), [
+			artist,
+			mint,
+		]);
+
+		const connection = provider.connection;
+		const treasuryBalance = await connection.getBalance(treasury);
+		const buyerAccessoryAta = await getATA(mint.publicKey, collector.publicKey);
+
+		const tx = new Transaction().add(
+			await program.methods
+				.fillCollectionOffer()
+				.accountsStrict({
+					seller: artist.publicKey,
+					buyer: collector.publicKey,
+					buyerAvatarMint: avatarMint,
+					buyerAvatarMetadata: avatarMetadata,
+
+					accessoryMint: mint.publicKey,
+					accessoryMetadata: await getMetadataAccount(mint.publicKey),
+					accessoryRecord: await getAccessoryRecordPDA(mint.publicKey),
+					sellerAccessoryAta: await getATA(mint.publicKey, artist.publicKey),
+					buyerAccessoryAta,
+					collectionOffer: slotOffer,
+
+					config,
+					treasury,
+
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.remainingAccounts([
+					{ pubkey: artist.publicKey, isSigner: false, isWritable: true },
+				])
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [artist]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+
+		assert.equal(
+			(await connection.getBalance(treasury)) - treasuryBalance,
+			Math.floor((price * fee) / 10_000)
+		);
+		// The last unit was bought, so the offer is closed
+		assert.equal(await connection.getAccountInfo(slotOffer), null);
+		assert.equal(
+			(await connection.getTokenAccountBalance(buyerAccessoryAta)).value
+				.amount,
+			"1"
+		);
+	});
+
+	it("Should reject collection offer fills outside the slot", async () => {
+		const offerId = collectionOfferId + 2;
+
+		const slotOffer = await getCollectionOfferPDA(
+			config,
+			collector.publicKey,
+			offerId
+		);
+
+		await provider.sendAndConfirm(
+			new Transaction().add(
+				await program.methods
+					.makeCollectionOffer(
+						new BN(offerId),
+						{ slot: { slot: accessorySlot + 1 } },
+						new BN(0.001 * LAMPORTS_PER_SOL),
+						1
+					)
+					.accountsStrict({
+						buyer: collector.publicKey,
+						config,
+						collectionOffer: slotOffer,
+						systemProgram: SYSTEM_PROGRAM_ADDRESS,
+					})
+					.instruction()
+			),
+			[collector]
+		);
+
+		const mint = Keypair.generate();
+		await provider.sendAndConfirm(await mintAccessoryTx(mint, null), [
+			artist,
+			mint,
+		]);
+
+		const tx = new Transaction().add(
+			await program.methods
+				.fillCollectionOffer()
+				.accountsStrict({
+					seller: artist.publicKey,
+					buyer: collector.publicKey,
+					buyerAvatarMint: avatarMint,
+					buyerAvatarMetadata: avatarMetadata,
+
+					accessoryMint: mint.publicKey,
+					accessoryMetadata: await getMetadataAccount(mint.publicKey),
+					accessoryRecord: await getAccessoryRecordPDA(mint.publicKey),
+					sellerAccessoryAta: await getATA(mint.publicKey, artist.publicKey),
+					buyerAccessoryAta: await getATA(mint.publicKey, collector.publicKey),
+					collectionOffer: slotOffer,
+
+					config,
+					treasury,
+
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.remainingAccounts([
+					{ pubkey: artist.publicKey, isSigner: false, isWritable: true },
+				])
+				.instruction()
+		);
+
+		await assertFailsWith(tx, [artist], "OfferFilterMismatch");
+
+		await provider.sendAndConfirm(
+			new Transaction().add(
+				await program.methods
+					.cancelCollectionOffer()
+					.accountsStrict({
+						buyer: collector.publicKey,
+						config,
+						collectionOffer: slotOffer,
+						systemProgram: SYSTEM_PROGRAM_ADDRESS,
+					})
+					.instruction()
+			),
+			[collector]
+		);
+	});
+
+	it("Should list an accessory reserved for the collector", async () => {
+		const price = 0.001 * LAMPORTS_PER_SOL;
+
+		const mint = await mintAndListAccessory(price, {
+			reservedBuyer: collector.publicKey,
+		});
+		const listing = await program.account.listing.fetch(
+			await getListingPDA(mint.publicKey, artist.publicKey)
+		);
+
+		assert.ok(listing.reservedBuyer.equals(collector.publicKey));
+	});
+
+	async function buyAccessoryTx(
+		mint: PublicKey,
+		buyer: Keypair,
+		expectedPrice: number = 0.001 * LAMPORTS_PER_SOL,
+		expectedPaymentMint: PublicKey | null = null
+	): Promise<Transaction> {
+		const listing = await getListingPDA(mint, artist.publicKey);
+		const buyerAvatarMint = await getAvatarMintPDA(
+			buyer.publicKey,
+			avatarCollection
+		);
+
+		return new Transaction().add(
+			await program.methods
+				.buyAccessory(new BN(expectedPrice), expectedPaymentMint)
+				.accountsStrict({
+					collector: buyer.publicKey,
+					collectorAvatarMint: buyerAvatarMint,
+					collectorAvatarMetadata: await getMetadataAccount(buyerAvatarMint),
+					collectorAccessoryAta: await getATA(mint, buyer.publicKey),
+
+					config,
+					treasury,
+					listing,
+					listingVault: await getATA(mint, listing),
+					artistAccessoryAta: null,
+					accessoryMasterEdition: null,
+					sellerProceeds: null,
+
+					artist: artist.publicKey,
+					artistPayout: artist.publicKey,
+					accessoryMint: mint,
+					accessoryRecord: await getAccessoryRecordPDA(mint),
+
+					paymentMint: null,
+					collectorPaymentAta: null,
+					artistPaymentAta: null,
+					treasuryPaymentAta: null,
+
+					referrer: null,
+					referrerPaymentAta: null,
+
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				// The artist is the only creator in the accessory record
+				.remainingAccounts([
+					{ pubkey: artist.publicKey, isSigner: false, isWritable: true },
+				])
+				.instruction()
+		);
+	}
+
+	it("Should reject a reserved listing bought by another buyer", async () => {
+		const mint = await mintAndListAccessory(0.001 * LAMPORTS_PER_SOL, {
+			reservedBuyer: collector.publicKey,
+		});
+
+		// The payer holds an avatar too, but the listing is reserved for the collector
+		await assertFailsWith(
+			await buyAccessoryTx(mint.publicKey, payer),
+			[payer],
+			"ReservedForAnotherBuyer"
+		);
+	});
+
+	it("Should let the reserved buyer buy the listing", async () => {
+		const mint = await mintAndListAccessory(0.001 * LAMPORTS_PER_SOL, {
+			reservedBuyer: collector.publicKey,
+		});
+		const listing = await getListingPDA(mint.publicKey, artist.publicKey);
+
+		const sig = await provider.sendAndConfirm(
+			await buyAccessoryTx(mint.publicKey, collector),
+			[collector]
+		);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+
+		assert.equal(await provider.connection.getAccountInfo(listing), null);
+		assert.equal(
+			(
+				await provider.connection.getTokenAccountBalance(
+					await getATA(mint.publicKey, collector.publicKey)
+				)
+			).value.amount,
+			"1"
+		);
+	});
+
+	it("Should buy a listed accessory with SPL tokens", async () => {
+		const connection = provider.connection;
+		const price = 1_000_000; // 1 token with 6 decimals
+		const paymentMint = Keypair.generate();
+
+		const setupTx = new Transaction().add(
+			...(await createPaymentMintInstructions(
+				connection,
+				payer.publicKey,
+				paymentMint.publicKey,
+				6
+			)),
+			...(await mintPaymentTokensInstructions(
+				payer.publicKey,
+				paymentMint.publicKey,
+				collector.publicKey,
+				price
+			)),
+			await program.methods
+				.setPaymentMints([usdcMint, paymentMint.publicKey])
+				.accountsStrict({
+					admin: payer.publicKey,
+					config,
+				})
+				.instruction()
+		);
+
+		await provider.sendAndConfirm(setupTx, [payer, paymentMint]);
+
+		const mint = await mintAndListAccessory(price, {
+			paymentMint: paymentMint.publicKey,
+			creators: [
+				{ address: artist.publicKey, share: 70 },
+				{ address: payer.publicKey, share: 30 },
+			],
+		});
+		const listing = await getListingPDA(mint.publicKey, artist.publicKey);
+
+		const collectorPaymentAta = await getATA(
+			paymentMint.publicKey,
+			collector.publicKey
+		);
+		const artistPaymentAta = await getATA(
+			paymentMint.publicKey,
+			artist.publicKey
+		);
+		const payerPaymentAta = await getATA(
+			paymentMint.publicKey,
+			payer.publicKey
+		);
+		const treasuryPaymentAta = await getATA(paymentMint.publicKey, treasury);
+
+		const tx = new Transaction().add(
+			await program.methods
+				.buyAccessory(new BN(price), paymentMint.publicKey)
+				.accountsStrict({
+					collector: collector.publicKey,
+					collectorAvatarMint: avatarMint,
+					collectorAvatarMetadata: avatarMetadata,
+					collectorAccessoryAta: await getATA(
+						mint.publicKey,
+						collector.publicKey
+					),
+
+					config,
+					treasury,
+					listing,
+					listingVault: await getATA(mint.publicKey, listing),
+					artistAccessoryAta: null,
+					accessoryMasterEdition: null,
+					sellerProceeds: null,
+
+					artist: artist.publicKey,
+					artistPayout: artist.publicKey,
+					accessoryMint: mint.publicKey,
+					accessoryRecord: await getAccessoryRecordPDA(mint.publicKey),
+
+					paymentMint: paymentMint.publicKey,
+					collectorPaymentAta,
+					artistPaymentAta,
+					treasuryPaymentAta,
+
+					referrer: null,
+					referrerPaymentAta: null,
+
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				// One creator and creator token account pair per creator in the accessory record
+				.remainingAccounts([
+					{ pubkey: artist.publicKey, isSigner: false, isWritable: false },
+					{ pubkey: artistPaymentAta, isSigner: false, isWritable: true },
+					{ pubkey: payer.publicKey, isSigner: false, isWritable: false },
+					{ pubkey: payerPaymentAta, isSigner: false, isWritable: true },
+				])
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(tx, [collector]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+
+		const balanceOf = async (ata: PublicKey) =>
+			Number((await connection.getTokenAccountBalance(ata)).value.amount);
+
+		// On the primary sale the creators split everything left after the fee,
+		// the artist's share is paid to them as the seller
+		const feeAmount = Math.floor((price * fee) / 10_000);
+		const payerShare = Math.floor(((price - feeAmount) * 30) / 100);
+
+		assert.equal(await balanceOf(collectorPaymentAta), 0);
+		assert.equal(await balanceOf(treasuryPaymentAta), feeAmount);
+		assert.equal(await balanceOf(payerPaymentAta), payerShare);
+		assert.equal(
+			await balanceOf(artistPaymentAta),
+			price - feeAmount - payerShare
+		);
+		assert.equal(
+			await balanceOf(await getATA(mint.publicKey, collector.publicKey)),
+			1
+		);
+	});
+
+	async function chainTime(): Promise<number> {
+		const connection = provider.connection;
+		return await connection.getBlockTime(await connection.getSlot());
+	}
+
+	async function waitUntilChainTime(timestamp: number) {
+		while ((await chainTime()) <= timestamp) {
+			await new Promise((resolve) => setTimeout(resolve, 1_000));
+		}
+	}
+
+	async function expireListingTx(
+		mint: PublicKey,
+		delegated: boolean = false
+	): Promise<Transaction> {
+		const listing = await getListingPDA(mint, artist.publicKey);
+
+		return new Transaction().add(
+			await program.methods
+				.expireListing()
+				.accountsStrict({
+					cranker: payer.publicKey,
+					artist: artist.publicKey,
+					artistAccessoryAta: await getATA(mint, artist.publicKey),
+
+					listing,
+					listingVault: await getATA(mint, listing),
+
+					accessoryMint: mint,
+					accessoryMasterEdition: delegated
+						? await getMasterEdition(mint)
+						: null,
+
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.instruction()
+		);
+	}
+
+	it("Should expire a listing only once its expiry has passed", async () => {
+		const connection = provider.connection;
+		const expiresAt = (await chainTime()) + 10;
+
+		const mint = await mintAndListAccessory(0.001 * LAMPORTS_PER_SOL, {
+			expiresAt,
+		});
+		const listing = await getListingPDA(mint.publicKey, artist.publicKey);
+		const artistAta = await getATA(mint.publicKey, artist.publicKey);
+
+		await assertFailsWith(
+			await expireListingTx(mint.publicKey),
+			[payer],
+			"ListingNotExpired"
+		);
+
+		await waitUntilChainTime(expiresAt);
+
+		await assertFailsWith(
+			await buyAccessoryTx(mint.publicKey, collector),
+			[collector],
+			"ListingExpired"
+		);
+
+		const rent =
+			(await connection.getBalance(listing)) +
+			(await connection.getBalance(await getATA(mint.publicKey, listing)));
+		const artistBalance = await connection.getBalance(artist.publicKey);
+
+		const sig = await provider.sendAndConfirm(
+			await expireListingTx(mint.publicKey),
+			[payer]
+		);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+
+		assert.equal(await connection.getAccountInfo(listing), null);
+		assert.equal(
+			(await connection.getTokenAccountBalance(artistAta)).value.amount,
+			"1"
+		);
+		assert.equal(
+			(await connection.getBalance(artist.publicKey)) - artistBalance,
+			rent
+		);
+	});
+
+	it("Should clear the listing delegate when a delegated listing expires", async () => {
+		const connection = provider.connection;
+		const expiresAt = (await chainTime()) + 10;
+
+		const mint = Keypair.generate();
+
+		await provider.sendAndConfirm(await mintAccessoryTx(mint, null), [
+			artist,
+			mint,
+		]);
+
+		const artistAta = await getATA(mint.publicKey, artist.publicKey);
+		const listing = await getListingPDA(mint.publicKey, artist.publicKey);
+
+		const listTx = new Transaction().add(
+			await program.methods
+				.listAccessoryDelegated(
+					new BN(0.001 * LAMPORTS_PER_SOL),
+					null,
+					new BN(expiresAt),
+					null
+				)
+				.accountsStrict({
+					artist: artist.publicKey,
+					artistAccessoryAta: artistAta,
+
+					config,
+					listing,
+					sellerProceeds: null,
+
+					accessoryMint: mint.publicKey,
+					accessoryMetadata: await getMetadataAccount(mint.publicKey),
+					accessoryCollection,
+					accessoryMasterEdition: await getMasterEdition(mint.publicKey),
+
+					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.instruction()
+		);
+
+		await provider.sendAndConfirm(listTx, [artist]);
+
+		await waitUntilChainTime(expiresAt);
+
+		const sig = await provider.sendAndConfirm(
+			await expireListingTx(mint.publicKey, true),
+			[payer]
+		);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+
+		const artistAtaInfo = (await connection.getParsedAccountInfo(artistAta))
+			.value.data as any;
+
+		assert.equal(artistAtaInfo.parsed.info.tokenAmount.amount, "1");
+		assert.equal(artistAtaInfo.parsed.info.state, "initialized");
+		assert.equal(artistAtaInfo.parsed.info.delegate, undefined);
+		assert.equal(await connection.getAccountInfo(listing), null);
+		assert.equal(
+			await connection.getAccountInfo(await getATA(mint.publicKey, listing)),
+			null
+		);
+	});
+
+	it("Should reject purchases made against stale listing terms", async () => {
+		const oldPrice = 0.001 * LAMPORTS_PER_SOL;
+		const newPrice = 0.002 * LAMPORTS_PER_SOL;
+
+		const mint = await mintAndListAccessory(oldPrice);
+
+		const tx = new Transaction().add(
+			await program.methods
+				.updateListingPrice(new BN(newPrice))
+				.accountsStrict({
+					artist: artist.publicKey,
+					listing: await getListingPDA(mint.publicKey, artist.publicKey),
+					accessoryMint: mint.publicKey,
+				})
+				.instruction()
+		);
+
+		await provider.sendAndConfirm(tx, [artist]);
+
+		// The buyer still expects the price from before the update
+		await assertFailsWith(
+			await buyAccessoryTx(mint.publicKey, collector, oldPrice),
+			[collector],
+			"UnexpectedPrice"
+		);
+
+		// The listing is priced in lamports, not in USDC
+		await assertFailsWith(
+			await buyAccessoryTx(mint.publicKey, collector, newPrice, usdcMint),
+			[collector],
+			"UnexpectedPaymentMint"
+		);
+	});
+
+	it("Should buy a dutch auction listing at its current price", async () => {
+		const connection = provider.connection;
+		const startPrice = 0.002 * LAMPORTS_PER_SOL;
+		const endPrice = 0.001 * LAMPORTS_PER_SOL;
+		const now = await chainTime();
+
+		const mint = await mintAndListAccessory(startPrice, {
+			dutchAuction: {
+				startPrice: new BN(startPrice),
+				endPrice: new BN(endPrice),
+				startTime: new BN(now - 10),
+				endTime: new BN(now + 60),
+				curve: { linear: {} },
+			},
+		});
+		const listing = await getListingPDA(mint.publicKey, artist.publicKey);
+
+		const quotedPrice = (
+			await program.methods
+				.getListingPrice()
+				.accountsStrict({
+					artist: artist.publicKey,
+					listing,
+					accessoryMint: mint.publicKey,
+				})
+				.view()
+		).toNumber();
+
+		assert.ok(quotedPrice < startPrice && quotedPrice >= endPrice);
+
+		const rent =
+			(await connection.getBalance(listing)) +
+			(await connection.getBalance(await getATA(mint.publicKey, listing)));
+		const artistBalance = await connection.getBalance(artist.publicKey);
+
+		// The price only decays, so the quote is the most the buyer can be charged
+		const sig = await provider.sendAndConfirm(
+			await buyAccessoryTx(mint.publicKey, collector, quotedPrice),
+			[collector]
+		);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+
+		const afterFee = (price: number) =>
+			price - Math.floor((price * fee) / 10_000);
+		const received =
+			(await connection.getBalance(artist.publicKey)) - artistBalance - rent;
+
+		assert.ok(received <= afterFee(quotedPrice));
+		assert.ok(received >= afterFee(endPrice));
+		assert.equal(await connection.getAccountInfo(listing), null);
+	});
+
+	async function mintAndListBundle(
+		id: number,
+		price: number,
+		size: number
+	): Promise<PublicKey[]> {
+		const mints: PublicKey[] = [];
+
+		for (let i = 0; i < size; i++) {
+			const mint = Keypair.generate();
+
+			await provider.sendAndConfirm(await mintAccessoryTx(mint, null), [
+				artist,
+				mint,
+			]);
+
+			mints.push(mint.publicKey);
+		}
+
+		const bundle = await getBundlePDA(artist.publicKey, id);
+		const accounts: AccountMeta[] = [];
+
+		for (const mint of mints) {
+			accounts.push(
+				{ pubkey: mint, isSigner: false, isWritable: false },
+				{
+					pubkey: await getAccessoryRecordPDA(mint),
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: await getATA(mint, artist.publicKey),
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: await getATA(mint, bundle),
+					isSigner: false,
+					isWritable: true,
+				}
+			);
+		}
+
+		const tx = new Transaction().add(
+			await program.methods
+				.listBundle(new BN(id), new BN(price))
+				.accountsStrict({
+					seller: artist.publicKey,
+					bundle,
+					config,
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.remainingAccounts(accounts)
+				.instruction()
+		);
+
+		await provider.sendAndConfirm(tx, [artist]);
+
+		return mints;
+	}
+
+	async function buyBundleTx(
+		id: number,
+		price: number,
+		mints: PublicKey[],
+		buyer: Keypair,
+		withAvatar: boolean = true
+	): Promise<Transaction> {
+		const bundle = await getBundlePDA(artist.publicKey, id);
+		const buyerAvatarMint = await getAvatarMintPDA(
+			buyer.publicKey,
+			avatarCollection
+		);
+		const accounts: AccountMeta[] = [];
+
+		for (const mint of mints) {
+			accounts.push(
+				{ pubkey: mint, isSigner: false, isWritable: false },
+				{
+					pubkey: await getAccessoryRecordPDA(mint),
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: await getATA(mint, bundle),
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: await getATA(mint, buyer.publicKey),
+					isSigner: false,
+					isWritable: true,
+				}
+			);
+		}
+
+		// Bundled accessories are minted without creators, so each one pays the artist
+		accounts.push(
+			...mints.map(() => ({
+				pubkey: artist.publicKey,
+				isSigner: false,
+				isWritable: true,
+			}))
+		);
+
+		return new Transaction()
+			.add(ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }))
+			.add(
+				await program.methods
+					.buyBundle(new BN(price))
+					.accountsStrict({
+						collector: buyer.publicKey,
+						collectorAvatarMint: withAvatar ? buyerAvatarMint : null,
+						collectorAvatarMetadata: withAvatar
+							? await getMetadataAccount(buyerAvatarMint)
+							: null,
+
+						seller: artist.publicKey,
+						bundle,
+
+						config,
+						treasury,
+
+						tokenProgram: TOKEN_PROGRAM_ADDRESS,
+						associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+						metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+						systemProgram: SYSTEM_PROGRAM_ADDRESS,
+					})
+					.remainingAccounts(accounts)
+					.instruction()
+			);
+	}
+
+	it("Should buy a bundle of accessories", async () => {
+		const connection = provider.connection;
+		const id = 2;
+		const price = 0.004 * LAMPORTS_PER_SOL;
+
+		const mints = await mintAndListBundle(id, price, 2);
+		const bundle = await getBundlePDA(artist.publicKey, id);
+
+		let rent = await connection.getBalance(bundle);
+		for (const mint of mints) {
+			rent += await connection.getBalance(await getATA(mint, bundle));
+		}
+
+		const treasuryBalance = await connection.getBalance(treasury);
+		const artistBalance = await connection.getBalance(artist.publicKey);
+
+		const sig = await provider.sendAndConfirm(
+			await buyBundleTx(id, price, mints, collector),
+			[collector]
+		);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+
+		const feeAmount = Math.floor((price * fee) / 10_000);
+
+		for (const mint of mints) {
+			assert.equal(
+				(
+					await connection.getTokenAccountBalance(
+						await getATA(mint, collector.publicKey)
+					)
+				).value.amount,
+				"1"
+			);
+			assert.equal(
+				await connection.getAccountInfo(await getATA(mint, bundle)),
+				null
+			);
+		}
+		assert.equal(await connection.getAccountInfo(bundle), null);
+		assert.equal(
+			(await connection.getBalance(treasury)) - treasuryBalance,
+			feeAmount
+		);
+		assert.equal(
+			(await connection.getBalance(artist.publicKey)) - artistBalance,
+			price - feeAmount + rent
+		);
+	});
+
+	async function giftWithAvatarTx(
+		accessoryMint: PublicKey,
+		recipient: PublicKey,
+		avatarMint: PublicKey
+	): Promise<Transaction> {
+		return new Transaction().add(
+			await program.methods
+				.giftAccessory(null)
+				.accountsStrict({
+					sender: artist.publicKey,
+					recipient,
+					avatarMint,
+					avatarMetadata: await getMetadataAccount(avatarMint),
+					recipientAvatarAta: await getATA(avatarMint, recipient),
+
+					accessoryMint,
+					accessoryMetadata: await getMetadataAccount(accessoryMint),
+					senderAccessoryAta: await getATA(accessoryMint, artist.publicKey),
+					recipientAccessoryAta: await getATA(accessoryMint, recipient),
+
+					config,
+
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.instruction()
+		);
+	}
+
+	it("Should reject a gift to an avatar outside the avatar collection", async () => {
+		const mint = Keypair.generate();
+
+		await provider.sendAndConfirm(await mintAccessoryTx(mint, null), [
+			artist,
+			mint,
+		]);
+
+		// The artist's accessory is a verified NFT, but of the accessory collection
+		await assertFailsWith(
+			await giftWithAvatarTx(mint.publicKey, artist.publicKey, mint.publicKey),
+			[artist],
+			"AvatarWrongCollection"
+		);
+	});
+
+	it("Should reject a gift to an avatar without a collection", async () => {
+		const mint = Keypair.generate();
+
+		await provider.sendAndConfirm(await mintAccessoryTx(mint, null), [
+			artist,
+			mint,
+		]);
+
+		// The accessory collection NFT is held by the config and is not in a collection itself
+		await assertFailsWith(
+			await giftWithAvatarTx(mint.publicKey, config, accessoryCollection),
+			[artist],
+			"AvatarMissingCollection"
+		);
+	});
+
+	it("Should reject listing an avatar as an accessory", async () => {
+		const listing = await getListingPDA(avatarMint, collector.publicKey);
+
+		const tx = new Transaction().add(
+			await program.methods
+				.listAccessory(new BN(0.001 * LAMPORTS_PER_SOL), null, null, null)
+				.accountsStrict({
+					artist: collector.publicKey,
+					artistAccessoryAta: await getATA(avatarMint, collector.publicKey),
+
+					config,
+					listing,
+					listingVault: await getATA(avatarMint, listing),
+					sellerProceeds: null,
+
+					// The collector's avatar is a verified NFT, but of the avatar collection
+					accessoryMint: avatarMint,
+					accessoryMetadata: avatarMetadata,
+					accessoryCollection,
+					accessoryMasterEdition: avatarMasterEdition,
+
+					metadataProgram: TOKEN_METADATA_PROGRAM_ADDRESS,
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.instruction()
+		);
+
+		await assertFailsWith(tx, [collector], "AccessoryWrongCollection");
+	});
+
+	it("Should fill a swap of accessories with a SOL top-up", async () => {
+		const connection = provider.connection;
+		const id = 2;
+		const solTopUp = 0.002 * LAMPORTS_PER_SOL;
+		const swap = await getSwapPDA(artist.publicKey, id);
+
+		const offeredMint = Keypair.generate();
+		const requestedMint = Keypair.generate();
+
+		for (const mint of [offeredMint, requestedMint]) {
+			await provider.sendAndConfirm(await mintAccessoryTx(mint, null), [
+				artist,
+				mint,
+			]);
+		}
+
+		// The collector holds the requested accessory
+		await provider.sendAndConfirm(
+			await giftWithAvatarTx(
+				requestedMint.publicKey,
+				collector.publicKey,
+				avatarMint
+			),
+			[artist]
+		);
+
+		const swapVault = await getATA(offeredMint.publicKey, swap);
+
+		const makeTx = new Transaction().add(
+			await program.methods
+				.makeSwap(new BN(id), [requestedMint.publicKey], new BN(solTopUp))
+				.accountsStrict({
+					maker: artist.publicKey,
+					swap,
+					config,
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.remainingAccounts([
+					{ pubkey: offeredMint.publicKey, isSigner: false, isWritable: false },
+					{
+						pubkey: await getMetadataAccount(offeredMint.publicKey),
+						isSigner: false,
+						isWritable: false,
+					},
+					{
+						pubkey: await getATA(offeredMint.publicKey, artist.publicKey),
+						isSigner: false,
+						isWritable: true,
+					},
+					{ pubkey: swapVault, isSigner: false, isWritable: true },
+				])
+				.instruction()
+		);
+
+		await provider.sendAndConfirm(makeTx, [artist]);
+
+		const rent =
+			(await connection.getBalance(swap)) +
+			(await connection.getBalance(swapVault));
+		const treasuryBalance = await connection.getBalance(treasury);
+		const artistBalance = await connection.getBalance(artist.publicKey);
+
+		const collectorOfferedAta = await getATA(
+			offeredMint.publicKey,
+			collector.publicKey
+		);
+		const artistRequestedAta = await getATA(
+			requestedMint.publicKey,
+			artist.publicKey
+		);
+
+		const fillTx = new Transaction().add(
+			await program.methods
+				.fillSwap()
+				.accountsStrict({
+					taker: collector.publicKey,
+					maker: artist.publicKey,
+					swap,
+					config,
+					treasury,
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.remainingAccounts([
+					// Requested accessories, sent by the taker
+					{
+						pubkey: requestedMint.publicKey,
+						isSigner: false,
+						isWritable: false,
+					},
+					{
+						pubkey: await getMetadataAccount(requestedMint.publicKey),
+						isSigner: false,
+						isWritable: false,
+					},
+					{
+						pubkey: await getATA(requestedMint.publicKey, collector.publicKey),
+						isSigner: false,
+						isWritable: true,
+					},
+					{ pubkey: artistRequestedAta, isSigner: false, isWritable: true },
+					// Offered accessories, received by the taker
+					{ pubkey: offeredMint.publicKey, isSigner: false, isWritable: false },
+					{ pubkey: swapVault, isSigner: false, isWritable: true },
+					{ pubkey: collectorOfferedAta, isSigner: false, isWritable: true },
+				])
+				.instruction()
+		);
+
+		const sig = await provider.sendAndConfirm(fillTx, [collector]);
+
+		console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+
+		assert.equal(
+			(await connection.getTokenAccountBalance(collectorOfferedAta)).value
+				.amount,
+			"1"
+		);
+		assert.equal(
+			(await connection.getTokenAccountBalance(artistRequestedAta)).value
+				.amount,
+			"1"
+		);
+		assert.equal(await connection.getAccountInfo(swap), null);
+		assert.equal(
+			(await connection.getBalance(treasury)) - treasuryBalance,
+			swapFee
+		);
+		assert.equal(
+			(await connection.getBalance(artist.publicKey)) - artistBalance,
+			solTopUp + rent
+		);
+	});
+
+	for (const cartSize of [2, 4, 8]) {
+		it(`Should buy a cart of ${cartSize} accessories`, async () => {
+			const price = 0.001 * LAMPORTS_PER_SOL;
+			const connection = provider.connection;
+
+			const cart: AccountMeta[] = [];
+			const mints: PublicKey[] = [];
+			const listings: PublicKey[] = [];
+
+			for (let i = 0; i < cartSize; i++) {
+				const mint = await mintAndListAccessory(price);
+				const listing = await getListingPDA(mint.publicKey, artist.publicKey);
+
+				mints.push(mint.publicKey);
+				listings.push(listing);
+
+				cart.push(
+					{ pubkey: listing, isSigner: false, isWritable: true },
+					{
+						pubkey: await getATA(mint.publicKey, listing),
+						isSigner: false,
+						isWritable: true,
+					},
+					{ pubkey: mint.publicKey, isSigner: false, isWritable: false },
+					{
+						pubkey: await getATA(mint.publicKey, collector.publicKey),
+						isSigner: false,
+						isWritable: true,
+					},
+					{ pubkey: artist.publicKey, isSigner: false, isWritable: true },
+					{
+						pubkey: await getAccessoryRecordPDA(mint.publicKey),
+						isSigner: false,
+						isWritable: true,
+					},
+					// The artist is the only creator of these accessories
+					{ pubkey: artist.publicKey, isSigner: false, isWritable: true }
+				);
+			}
+
+			// Larger carts only fit in a transaction through a lookup table
+			const slot = await provider.connection.getSlot("finalized");
+			const [createLookupTable, lookupTable] =
+				AddressLookupTableProgram.createLookupTable({
+					authority: payer.publicKey,
+					payer: payer.publicKey,
+					recentSlot: slot,
+				});
+
+			await provider.sendAndConfirm(
+				new Transaction().add(createLookupTable),
+				[payer]
+			);
+
+			const addresses = [
+				...new Set(cart.map((meta) => meta.pubkey.toBase58())),
+			].map((key) => new PublicKey(key));
+
+			for (let i = 0; i < addresses.length; i += 20) {
+				await provider.sendAndConfirm(
+					new Transaction().add(
+						AddressLookupTableProgram.extendLookupTable({
+							authority: payer.publicKey,
+							payer: payer.publicKey,
+							lookupTable,
+							addresses: addresses.slice(i, i + 20),
+						})
+					),
+					[payer]
+				);
+			}
+
+			// Wait for the lookup table to activate
+			await new Promise((resolve) => setTimeout(resolve, 2_000));
+
+			const lookupTableAccount = (
+				await provider.connection.getAddressLookupTable(lookupTable)
+			).value;
+
+			const instruction = await program.methods
+				.buyMany(new BN(price * cartSize))
+				.accountsStrict({
+					collector: collector.publicKey,
+					collectorAvatarMint: avatarMint,
+					collectorAvatarMetadata: avatarMetadata,
+
+					config,
+					treasury,
+
+					tokenProgram: TOKEN_PROGRAM_ADDRESS,
+					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.remainingAccounts(cart)
+				.instruction();
+
+			const { blockhash } = await provider.connection.getLatestBlockhash();
+
+			const message = new TransactionMessage({
+				payerKey: collector.publicKey,
+				recentBlockhash: blockhash,
+				instructions: [
+					ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 }),
+					instruction,
+				],
+			}).compileToV0Message([lookupTableAccount]);
+
+			const tx = new VersionedTransaction(message);
+			tx.sign([collector]);
+
+			let rent = 0;
+			for (const [i, listing] of listings.entries()) {
+				rent += await connection.getBalance(listing);
+				rent += await connection.getBalance(await getATA(mints[i], listing));
+			}
+
+			const treasuryBalance = await connection.getBalance(treasury);
+			const artistBalance = await connection.getBalance(artist.publicKey);
+
+			const sig = await provider.connection.sendTransaction(tx);
+			await provider.connection.confirmTransaction(sig, "confirmed");
+
+			console.log(`https://solscan.io/tx/${sig}?cluster=devnet`);
+
+			const confirmed = await provider.connection.getTransaction(sig, {
+				commitment: "confirmed",
+				maxSupportedTransactionVersion: 0,
+			});
+
+			console.log(
+				`buy_many with ${cartSize} items consumed ${confirmed.meta.computeUnitsConsumed} compute units`
+			);
+
+			// The artist sells their own accessories, so no royalty is owed
+			const feeAmount = Math.floor((price * fee) / 10_000);
+
+			for (const [i, listing] of listings.entries()) {
+				assert.equal(
+					(
+						await connection.getTokenAccountBalance(
+							await getATA(mints[i], collector.publicKey)
+						)
+					).value.amount,
+					"1"
+				);
+				assert.equal(await connection.getAccountInfo(listing), null);
+				assert.equal(
+					await connection.getAccountInfo(await getATA(mints[i], listing)),
+					null
+				);
+			}
+			assert.equal(
+				(await connection.getBalance(treasury)) - treasuryBalance,
+				feeAmount * cartSize
+			);
+			// The listing and vault rent go back to the seller with their payout
+			assert.equal(
+				(await connection.getBalance(artist.publicKey)) - artistBalance,
+				(price - feeAmount) * cartSize + rent
+			);
+		});
+	}
+
+	async function createAccessoryDrop(
+		start: number,
+		end: number,
+		supplyCap: number | null
+	) {
+		const tx = new Transaction().add(
+			await program.methods
+				.createDrop(
+					{ accessory: {} },
+					{
+						clock: { unixTimestamp: {} },
+						start: new BN(start),
+						end: new BN(end),
+						supplyCap: supplyCap === null ? null : new BN(supplyCap),
+						uriPrefix: "https://www.jsonkeeper.com/",
+						allowlistRoot: null,
+					}
+				)
+				.accountsStrict({
+					admin: payer.publicKey,
+					config,
+					drop: accessoryDrop,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.instruction()
+		);
+
+		await provider.sendAndConfirm(tx, [payer]);
+	}
+
+	async function closeAccessoryDrop() {
+		const tx = new Transaction().add(
+			await program.methods
+				.closeDrop()
+				.accountsStrict({
+					admin: payer.publicKey,
+					config,
+					drop: accessoryDrop,
+					systemProgram: SYSTEM_PROGRAM_ADDRESS,
+				})
+				.instruction()
+		);
+
+		await provider.sendAndConfirm(tx, [payer]);
+	}
+
+	it("Should reject accessory mints before the drop starts", async () => {
+		const now = Math.floor(Date.now() / 1000);
+		await createAccessoryDrop(now + 60 * 60, now + 2 * 60 * 60, null);
+
+		const mint = Keypair.generate();
+		await assertFailsWith(
+			await mintAccessoryTx(mint, accessoryDrop),
+			[artist, mint],
+			"DropNotStarted"
+		);
+
+		await closeAccessoryDrop();
+	});
+
+	it("Should reject accessory mints after the drop ends", async () => {
+		const now = Math.floor(Date.now() / 1000);
+		await createAccessoryDrop(now - 2 * 60, now - 60, null);
+
+		const mint = Keypair.generate();
+		await assertFailsWith(
+			await mintAccessoryTx(mint, accessoryDrop),
+			[artist, mint],
+			"DropEnded"
+		);
+
+		await closeAccessoryDrop();
+	});
+
+	it("Should reject accessory mints once the drop is sold out", async () => {
+		const now = Math.floor(Date.now() / 1000);
+		await createAccessoryDrop(now - 60, now + 60 * 60, 1);
+
+		const mint = Keypair.generate();
+		await provider.sendAndConfirm(await mintAccessoryTx(mint, accessoryDrop), [
+			artist,
+			mint,
+		]);
+
+		const soldOutMint = Keypair.generate();
+		await assertFailsWith(
+			await mintAccessoryTx(soldOutMint, accessoryDrop),
+			[artist, soldOutMint],
+			"DropSoldOut"
+		);
+
+		await closeAccessoryDrop();
+	});
 });
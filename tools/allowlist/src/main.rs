@@ -0,0 +1,209 @@
+//! Builds the allowlist merkle tree for a Shapely drop.
+//!
+//! Usage: `cargo run -p allowlist -- <addresses.csv>`
+//!
+//! The CSV holds one wallet address per line (an optional `address` header and
+//! any extra columns are ignored). The root and every wallet's proof are printed
+//! as JSON, ready to be passed to `set_drop_allowlist` and `mint_avatar`.
+
+use std::{collections::BTreeSet, env, fs, process, str::FromStr};
+
+use anchor_lang::prelude::Pubkey;
+use shapely::merkle::{leaf_hash, node_hash};
+
+struct MerkleTree {
+    /// Every level of the tree, from the leaves up to the root
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    fn new(leaves: Vec<[u8; 32]>) -> Self {
+        let mut levels = vec![leaves];
+
+        while levels.last().is_some_and(|level| level.len() > 1) {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => node_hash(left, right),
+                    // An odd node out is promoted to the next level as is
+                    [single] => *single,
+                    _ => unreachable!(),
+                })
+                .collect();
+
+            levels.push(next);
+        }
+
+        Self { levels }
+    }
+
+    fn root(&self) -> [u8; 32] {
+        self.levels
+            .last()
+            .and_then(|level| level.first())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn proof(&self, mut index: usize) -> Vec<[u8; 32]> {
+        let mut proof = Vec::new();
+
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+
+        proof
+    }
+}
+
+fn read_addresses(path: &str) -> Result<Vec<Pubkey>, String> {
+    let csv = fs::read_to_string(path).map_err(|e| format!("Failed to read {path}: {e}"))?;
+
+    let mut addresses = BTreeSet::new();
+
+    for (line_number, line) in csv.lines().enumerate() {
+        let field = line.split(',').next().unwrap_or_default().trim();
+
+        if field.is_empty() || (line_number == 0 && field.eq_ignore_ascii_case("address")) {
+            continue;
+        }
+
+        let address = Pubkey::from_str(field)
+            .map_err(|e| format!("Invalid address on line {}: {e}", line_number + 1))?;
+
+        addresses.insert(address);
+    }
+
+    Ok(addresses.into_iter().collect())
+}
+
+fn to_json(hash: &[u8; 32]) -> String {
+    let bytes: Vec<String> = hash.iter().map(u8::to_string).collect();
+    format!("[{}]", bytes.join(","))
+}
+
+fn main() {
+    let Some(path) = env::args().nth(1) else {
+        eprintln!("Usage: allowlist <addresses.csv>");
+        process::exit(1);
+    };
+
+    let addresses = match read_addresses(&path) {
+        Ok(addresses) if !addresses.is_empty() => addresses,
+        Ok(_) => {
+            eprintln!("No addresses found in {path}");
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let tree = MerkleTree::new(addresses.iter().map(leaf_hash).collect());
+
+    let proofs: Vec<String> = addresses
+        .iter()
+        .enumerate()
+        .map(|(index, address)| {
+            let proof: Vec<String> = tree.proof(index).iter().map(to_json).collect();
+            format!("    \"{address}\": [{}]", proof.join(","))
+        })
+        .collect();
+
+    println!("{{");
+    println!("  \"root\": {},", to_json(&tree.root()));
+    println!("  \"proofs\": {{");
+    println!("{}", proofs.join(",\n"));
+    println!("  }}");
+    println!("}}");
+}
+
+#[cfg(test)]
+mod tests {
+    use shapely::merkle::verify_proof;
+
+    use super::*;
+
+    fn build(count: usize) -> (Vec<Pubkey>, MerkleTree) {
+        let addresses: Vec<Pubkey> = (0..count).map(|_| Pubkey::new_unique()).collect();
+        let tree = MerkleTree::new(addresses.iter().map(leaf_hash).collect());
+
+        (addresses, tree)
+    }
+
+    #[test]
+    fn accepts_the_proof_of_every_address() {
+        for count in [1, 2, 5, 8] {
+            let (addresses, tree) = build(count);
+
+            for (index, address) in addresses.iter().enumerate() {
+                assert!(verify_proof(
+                    &tree.proof(index),
+                    &tree.root(),
+                    leaf_hash(address)
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_addresses_outside_the_tree() {
+        let (_, tree) = build(5);
+
+        assert!(!verify_proof(
+            &tree.proof(0),
+            &tree.root(),
+            leaf_hash(&Pubkey::new_unique())
+        ));
+    }
+
+    #[test]
+    fn rejects_another_address_proof() {
+        let (addresses, tree) = build(5);
+
+        assert!(!verify_proof(
+            &tree.proof(1),
+            &tree.root(),
+            leaf_hash(&addresses[0])
+        ));
+    }
+
+    #[test]
+    fn rejects_tampered_proofs() {
+        let (addresses, tree) = build(5);
+
+        let mut proof = tree.proof(2);
+        proof[0][0] ^= 1;
+        assert!(!verify_proof(
+            &proof,
+            &tree.root(),
+            leaf_hash(&addresses[2])
+        ));
+
+        let mut proof = tree.proof(2);
+        proof.pop();
+        assert!(!verify_proof(
+            &proof,
+            &tree.root(),
+            leaf_hash(&addresses[2])
+        ));
+    }
+
+    #[test]
+    fn rejects_proofs_against_another_root() {
+        let (addresses, tree) = build(5);
+        let (_, other_tree) = build(5);
+
+        assert!(!verify_proof(
+            &tree.proof(0),
+            &other_tree.root(),
+            leaf_hash(&addresses[0])
+        ));
+    }
+}